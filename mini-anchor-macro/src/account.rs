@@ -1,20 +1,91 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, spanned::Spanned, Error as SynError, Fields, Meta};
+use syn::{parse_macro_input, Error as SynError, Fields, Ident, ItemStruct, Meta, Type};
+
+pub fn account_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return SynError::new_spanned(
+            proc_macro2::TokenStream::from(attr),
+            "#[account] takes no arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
 
-pub fn account_impl(item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as syn::ItemStruct);
+    let input = parse_macro_input!(item as ItemStruct);
 
     let struct_name = &input.ident;
     let struct_name_str = struct_name.to_string();
-    let discriminator = generate_discriminator(&struct_name_str);
+    let discriminator = generate_discriminator("account", &struct_name_str);
+
+    let fields = match parse_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let space_calculation = &fields.space;
+
+    let definition = generate_struct(&input, &fields);
+    let serialization = generate_serialization(struct_name, &fields);
+
+    quote! {
+        #definition
+
+        impl #struct_name {
+            // 8-byte discriminator
+            pub const DISCRIMINATOR: [u8; 8] = [#(#discriminator),*];
+
+            // Dynamic space calculation with max_len support!
+            pub const SPACE: usize = 8 #(+ #space_calculation)*;
+
+            // Wrapper for serialization
+            pub fn try_serialize(&self, buf: &mut [u8]) -> Result<(), ::mini_anchor::solana_program::program_error::ProgramError> {
+                <Self as ::mini_anchor::AnchorSerialize>::serialize(self, buf)?;
+                Ok(())
+            }
+
+            // Wrapper for deserialization
+            pub fn try_deserialize(data: &[u8]) -> Result<Self, ::mini_anchor::solana_program::program_error::ProgramError> {
+                let (instance, _size) = <Self as ::mini_anchor::AnchorDeserialize>::deserialize(data)?;
+                Ok(instance)
+            }
+        }
 
+        impl ::mini_anchor::Discriminator for #struct_name {
+            const DISCRIMINATOR: [u8; 8] = #struct_name::DISCRIMINATOR;
+        }
+
+        impl ::mini_anchor::Space for #struct_name {
+            const SPACE: usize = #struct_name::SPACE;
+        }
+
+        // Accounts of this type must be owned by the program declared with `declare_id!`
+        impl ::mini_anchor::Owner for #struct_name {
+            fn owner() -> ::mini_anchor::solana_program::pubkey::Pubkey {
+                crate::ID
+            }
+        }
+
+        #serialization
+    }
+    .into()
+}
+
+// Named fields of an `#[account]` or `#[event]` struct, with the maximum serialized
+// size of each field
+pub(crate) struct SerializedFields<'a> {
+    pub names: Vec<&'a Ident>,
+    pub types: Vec<&'a Type>,
+    pub space: Vec<proc_macro2::TokenStream>,
+}
+
+pub(crate) fn parse_fields(input: &ItemStruct) -> Result<SerializedFields<'_>, SynError> {
     let fields = match &input.fields {
         Fields::Named(named) => &named.named,
         _ => {
-            return SynError::new(input.span(), "Only named fields are supported")
-                .to_compile_error()
-                .into();
+            return Err(SynError::new_spanned(
+                &input.fields,
+                "Only named fields are supported",
+            ));
         }
     };
 
@@ -26,9 +97,7 @@ pub fn account_impl(item: TokenStream) -> TokenStream {
         let field_name = match field.ident.as_ref() {
             Some(name) => name,
             None => {
-                return SynError::new(field.span(), "Expected named field")
-                    .to_compile_error()
-                    .into();
+                return Err(SynError::new_spanned(field, "Expected named field"));
             }
         };
         let field_type = &field.ty;
@@ -44,12 +113,10 @@ pub fn account_impl(item: TokenStream) -> TokenStream {
             match max_len {
                 Some(len) => quote! { 4 + #len },
                 None => {
-                    return SynError::new(
-                        field.span(),
+                    return Err(SynError::new_spanned(
+                        field,
                         "String fields must have a #[max_len(N)] attribute",
-                    )
-                    .into_compile_error()
-                    .into();
+                    ));
                 }
             }
         } else if is_vec_type(field_type) {
@@ -59,53 +126,53 @@ pub fn account_impl(item: TokenStream) -> TokenStream {
             match (max_len, inner_type) {
                 (Some(len), Some(inner)) => {
                     if is_vec_type(inner) {
-                        return SynError::new(field.span(), "Nested Vec<Vec<T>> is not supported")
-                            .into_compile_error()
-                            .into();
+                        return Err(SynError::new_spanned(
+                            field,
+                            "Nested Vec<Vec<T>> is not supported",
+                        ));
                     };
                     quote! { 4 + (#len * std::mem::size_of::<#inner>()) }
                 }
                 (None, _) => {
-                    return SynError::new(
-                        field.span(),
+                    return Err(SynError::new_spanned(
+                        field,
                         "Vec fields require #[max_len(N)] attribute",
-                    )
-                    .into_compile_error()
-                    .into()
+                    ))
                 }
                 (_, None) => {
-                    return SynError::new(field.span(), "Unable to determine inner type of Vec")
-                        .into_compile_error()
-                        .into()
+                    return Err(SynError::new_spanned(
+                        field,
+                        "Unable to determine inner type of Vec",
+                    ))
                 }
             }
         } else if is_option_type(field_type) {
             let inner_type = match extract_option_inner_type(field_type) {
                 Some(inner) => inner,
                 None => {
-                    return SynError::new(field.span(), "Unable to parse Option<T> inner type")
-                        .into_compile_error()
-                        .into()
+                    return Err(SynError::new_spanned(
+                        field,
+                        "Unable to parse Option<T> inner type",
+                    ))
                 }
             };
 
             // Disallow nested Option<Option<T>>
             if is_option_type(inner_type) {
-                return SynError::new(field.span(), "Nested Option<Option<T>> is not supported")
-                    .into_compile_error()
-                    .into();
+                return Err(SynError::new_spanned(
+                    field,
+                    "Nested Option<Option<T>> is not supported",
+                ));
             }
 
             if is_string_type(inner_type) {
                 match max_len {
                     Some(len) => quote! { 1 + (4 + #len) },
                     None => {
-                        return SynError::new(
-                            field.span(),
+                        return Err(SynError::new_spanned(
+                            field,
                             "Option<String> fields must have a #[max_len(N)] attribute",
-                        )
-                        .into_compile_error()
-                        .into()
+                        ))
                     }
                 }
             } else if is_vec_type(inner_type) {
@@ -115,20 +182,16 @@ pub fn account_impl(item: TokenStream) -> TokenStream {
                         quote! { 1 + (4 + (#len * std::mem::size_of::<#vec_inner_type>())) }
                     }
                     (None, _) => {
-                        return SynError::new(
-                            field.span(),
+                        return Err(SynError::new_spanned(
+                            field,
                             "Option<Vec<T>> fields require #[max_len(N)] attribute",
-                        )
-                        .into_compile_error()
-                        .into()
+                        ))
                     }
                     (_, None) => {
-                        return SynError::new(
-                            field.span(),
+                        return Err(SynError::new_spanned(
+                            field,
                             "Unable to determine inner type of Vec inside Option",
-                        )
-                        .into_compile_error()
-                        .into()
+                        ))
                     }
                 }
             } else {
@@ -141,7 +204,22 @@ pub fn account_impl(item: TokenStream) -> TokenStream {
         space_calculation.push(space_calc);
     }
 
+    Ok(SerializedFields {
+        names: field_names,
+        types: field_types,
+        space: space_calculation,
+    })
+}
+
+// The struct itself, with every field public and the `#[max_len]` helpers removed
+pub(crate) fn generate_struct(
+    input: &ItemStruct,
+    fields: &SerializedFields,
+) -> proc_macro2::TokenStream {
+    let struct_name = &input.ident;
     let vis = &input.vis;
+    let field_names = &fields.names;
+    let field_types = &fields.types;
 
     let attrs: Vec<_> = input
         .attrs
@@ -155,37 +233,31 @@ pub fn account_impl(item: TokenStream) -> TokenStream {
         #vis struct #struct_name {
             #(pub #field_names: #field_types),*
         }
+    }
+}
 
-        impl #struct_name {
-            // 8-byte discriminator
-            pub const DISCRIMINATOR: [u8; 8] = [#(#discriminator),*];
-
-            // Dynamic space calculation with max_len support!
-            pub const SPACE: usize = 8 #(+ #space_calculation)*;
-
-            // Wrapper for serialization
-            pub fn try_serialize(&self, buf: &mut [u8]) -> Result<(), ::mini_anchor::solana_program::program_error::ProgramError> {
-                <Self as ::mini_anchor::AnchorSerialize>::serialize(self, buf)?;
-                Ok(())
-            }
-
-            // Wrapper for deserialization
-            pub fn try_deserialize(data: &[u8]) -> Result<Self, ::mini_anchor::solana_program::program_error::ProgramError> {
-                let (instance, _size) = <Self as ::mini_anchor::AnchorDeserialize>::deserialize(data)?;
-                Ok(instance)
-            }
-        }
+// `AnchorSerialize` and `AnchorDeserialize`: the discriminator followed by each field
+// in declaration order
+pub(crate) fn generate_serialization(
+    struct_name: &Ident,
+    fields: &SerializedFields,
+) -> proc_macro2::TokenStream {
+    let field_names = &fields.names;
+    let field_types = &fields.types;
 
+    quote! {
         // Serialize implementation
         impl ::mini_anchor::AnchorSerialize for #struct_name {
             // Serialize into a byte slice
             fn serialize(&self, buf: &mut [u8]) -> Result<usize, ::mini_anchor::solana_program::program_error::ProgramError> {
-                if buf.len() < Self::SPACE {
+                // `SPACE` is the maximum size, a resized account only needs room for its
+                // current contents and each field checks its own bounds
+                if buf.len() < 8 {
                     return Err(::mini_anchor::solana_program::program_error::ProgramError::AccountDataTooSmall);
                 }
 
                 // Write discriminator
-                buf[..8].copy_from_slice(&Self::DISCRIMINATOR);
+                buf[..8].copy_from_slice(&<Self as ::mini_anchor::Discriminator>::DISCRIMINATOR);
                 let mut offset = 8;
 
                 #(
@@ -207,12 +279,12 @@ pub fn account_impl(item: TokenStream) -> TokenStream {
             // Deserialize from a byte slice
             fn deserialize(data: &[u8]) -> Result<(Self, usize), ::mini_anchor::solana_program::program_error::ProgramError> {
                 if data.len() < 8 { // At least need discriminator
-                    return Err(::mini_anchor::solana_program::program_error::ProgramError::AccountDataTooSmall);
+                    return Err(::mini_anchor::error::ErrorCode::AccountDiscriminatorNotFound.into());
                 }
 
                 // Check discriminator
-                if data[..8] != Self::DISCRIMINATOR {
-                    return Err(::mini_anchor::solana_program::program_error::ProgramError::InvalidAccountData);
+                if data[..8] != <Self as ::mini_anchor::Discriminator>::DISCRIMINATOR {
+                    return Err(::mini_anchor::error::ErrorCode::AccountDiscriminatorMismatch.into());
                 }
 
                 let mut offset = 8;
@@ -230,13 +302,13 @@ pub fn account_impl(item: TokenStream) -> TokenStream {
             }
         }
     }
-    .into()
 }
 
-fn generate_discriminator(name: &str) -> [u8; 8] {
+// First 8 bytes of sha256("<namespace>:<name>")
+pub(crate) fn generate_discriminator(namespace: &str, name: &str) -> [u8; 8] {
     use sha2::{Digest, Sha256};
 
-    let preimage = format!("account:{name}");
+    let preimage = format!("{namespace}:{name}");
     let hash = Sha256::digest(preimage.as_bytes());
 
     let mut discriminator = [0u8; 8];
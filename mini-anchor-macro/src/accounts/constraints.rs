@@ -0,0 +1,312 @@
+use proc_macro2::Span;
+use syn::{
+    parse::ParseStream, spanned::Spanned, Error as SynError, Expr, ExprArray, Field, Ident, Token,
+};
+
+// Constraints parsed from a field's #[account(...)] attributes
+#[derive(Default)]
+pub(crate) struct Constraints {
+    pub is_mut: bool,
+    pub is_signer: bool,
+    pub init: Option<InitConstraint>,
+    // Account created client-side, initialized here if its discriminator is still zero
+    pub zero: bool,
+    pub seeds: Option<SeedsConstraint>,
+    // Sibling fields whose key must match the account's field of the same name
+    pub has_one: Vec<WithError<Ident>>,
+    pub address: Option<WithError<Expr>>,
+    pub owner: Option<WithError<Expr>>,
+    // Free-form boolean expressions, checked in declaration order
+    pub raw: Vec<WithError<Expr>>,
+    // Field receiving the lamports when the account is closed on exit
+    pub close: Option<Ident>,
+    pub realloc: Option<ReallocConstraint>,
+    // Fields allowed to share this account's key
+    pub dup: Vec<Ident>,
+    // First key of the field's constraints, `None` for a field without any, which
+    // can also be a nested Accounts struct
+    pub key_span: Option<Span>,
+}
+
+// #[account(realloc = <expr>, realloc::payer = <field>, realloc::zero = <bool>)]
+pub(crate) struct ReallocConstraint {
+    pub space: Expr,
+    // Funds growth and receives the surplus rent when shrinking
+    pub payer: Ident,
+    // Defaults to `false`
+    pub zero: Option<Expr>,
+}
+
+// A constraint value with an optional `@ <error>` replacing the framework error
+pub(crate) struct WithError<T> {
+    pub value: T,
+    pub error: Option<Expr>,
+}
+
+// #[account(init, payer = <field>, space = <expr>)], or `init_if_needed` in place of `init`
+pub(crate) struct InitConstraint {
+    pub payer: Ident,
+    // Defaults to the account type's `Space::SPACE`
+    pub space: Option<Expr>,
+    // Existing accounts are loaded instead of created
+    pub if_needed: bool,
+}
+
+// #[account(seeds = [...], bump)] or #[account(seeds = [...], bump = <expr>)]
+pub(crate) struct SeedsConstraint {
+    pub seeds: Vec<Expr>,
+    // `None` searches for the canonical bump
+    pub bump: Option<Expr>,
+}
+
+pub(crate) fn parse_constraints(field: &Field) -> Result<Constraints, SynError> {
+    let mut is_mut = false;
+    let mut is_signer = false;
+    // Span of `init` / `init_if_needed`, and whether it was the latter
+    let mut init: Option<(Span, bool)> = None;
+    let mut zero = false;
+    let mut payer: Option<Ident> = None;
+    let mut space: Option<Expr> = None;
+    let mut seeds: Option<ExprArray> = None;
+    let mut bump: Option<(Span, Option<Expr>)> = None;
+    let mut has_one = Vec::new();
+    let mut address = None;
+    let mut owner = None;
+    let mut raw = Vec::new();
+    let mut close: Option<Ident> = None;
+    let mut realloc: Option<Expr> = None;
+    let mut realloc_payer: Option<Ident> = None;
+    let mut realloc_zero: Option<Expr> = None;
+    let mut dup = Vec::new();
+    let mut key_span: Option<Span> = None;
+
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("account")) {
+        attr.parse_nested_meta(|meta| {
+            key_span.get_or_insert(meta.path.span());
+
+            if meta.path.is_ident("mut") {
+                is_mut = true;
+            } else if meta.path.is_ident("signer") {
+                is_signer = true;
+            } else if meta.path.is_ident("init") {
+                init = Some((meta.path.span(), false));
+            } else if meta.path.is_ident("init_if_needed") {
+                if !cfg!(feature = "init-if-needed") {
+                    return Err(meta.error(
+                        "init_if_needed requires the `init-if-needed` feature of mini-anchor",
+                    ));
+                }
+                init = Some((meta.path.span(), true));
+            } else if meta.path.is_ident("zero") {
+                zero = true;
+            } else if meta.path.is_ident("payer") {
+                payer = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("space") {
+                space = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("seeds") {
+                seeds = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("bump") {
+                let value = if meta.input.peek(syn::Token![=]) {
+                    Some(meta.value()?.parse()?)
+                } else {
+                    None
+                };
+                bump = Some((meta.path.span(), value));
+            } else if meta.path.is_ident("has_one") {
+                has_one.push(parse_with_error(meta.value()?)?);
+            } else if meta.path.is_ident("address") {
+                address = Some(parse_with_error(meta.value()?)?);
+            } else if meta.path.is_ident("owner") {
+                owner = Some(parse_with_error(meta.value()?)?);
+            } else if meta.path.is_ident("constraint") {
+                raw.push(parse_with_error(meta.value()?)?);
+            } else if meta.path.is_ident("close") {
+                close = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("realloc") {
+                realloc = Some(meta.value()?.parse()?);
+            } else if is_namespaced(&meta.path, "realloc", "payer") {
+                realloc_payer = Some(meta.value()?.parse()?);
+            } else if is_namespaced(&meta.path, "realloc", "zero") {
+                realloc_zero = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("dup") {
+                dup.push(meta.value()?.parse()?);
+            } else {
+                return Err(unknown_constraint(&meta.path));
+            }
+            Ok(())
+        })?;
+    }
+
+    if let (Some((span, _)), true) = (init, zero) {
+        return Err(SynError::new(span, "init cannot be combined with zero"));
+    }
+
+    let init = match (init, payer) {
+        (Some((_, if_needed)), Some(payer)) => Some(InitConstraint {
+            payer,
+            space,
+            if_needed,
+        }),
+        (Some((span, if_needed)), None) => {
+            let name = if if_needed { "init_if_needed" } else { "init" };
+            return Err(SynError::new(
+                span,
+                format!("{} requires `payer = <field>`", name),
+            ));
+        }
+        (None, Some(payer)) => {
+            return Err(SynError::new(payer.span(), "payer is only valid with init"));
+        }
+        (None, None) => {
+            if let Some(space) = space {
+                return Err(SynError::new(space.span(), "space is only valid with init"));
+            }
+            None
+        }
+    };
+
+    let seeds = match (seeds, bump) {
+        (Some(seeds), Some((_, bump))) => Some(SeedsConstraint {
+            seeds: seeds.elems.into_iter().collect(),
+            bump,
+        }),
+        (Some(seeds), None) => {
+            return Err(SynError::new(seeds.span(), "seeds requires `bump`"));
+        }
+        (None, Some((span, _))) => {
+            return Err(SynError::new(span, "bump requires `seeds = [...]`"));
+        }
+        (None, None) => None,
+    };
+
+    let realloc = match (realloc, realloc_payer) {
+        (Some(space), Some(payer)) => Some(ReallocConstraint {
+            space,
+            payer,
+            zero: realloc_zero,
+        }),
+        (Some(space), None) => {
+            return Err(SynError::new(
+                space.span(),
+                "realloc requires `realloc::payer = <field>`",
+            ));
+        }
+        (None, Some(payer)) => {
+            return Err(SynError::new(
+                payer.span(),
+                "realloc::payer is only valid with realloc",
+            ));
+        }
+        (None, None) => {
+            if let Some(zero) = realloc_zero {
+                return Err(SynError::new(
+                    zero.span(),
+                    "realloc::zero is only valid with realloc",
+                ));
+            }
+            None
+        }
+    };
+
+    Ok(Constraints {
+        // Creating or initializing an account writes to it
+        is_mut: is_mut || init.is_some() || zero,
+        is_signer,
+        init,
+        zero,
+        seeds,
+        has_one,
+        address,
+        owner,
+        raw,
+        close,
+        realloc,
+        dup,
+        key_span,
+    })
+}
+
+// Every key accepted inside #[account(...)]
+const CONSTRAINT_KEYS: &[&str] = &[
+    "mut",
+    "signer",
+    "init",
+    "init_if_needed",
+    "zero",
+    "payer",
+    "space",
+    "seeds",
+    "bump",
+    "has_one",
+    "address",
+    "owner",
+    "constraint",
+    "close",
+    "realloc",
+    "realloc::payer",
+    "realloc::zero",
+    "dup",
+];
+
+// Points out the closest known key when the unknown one looks like a typo
+fn unknown_constraint(path: &syn::Path) -> SynError {
+    let key = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+
+    let suggestion = CONSTRAINT_KEYS
+        .iter()
+        .map(|known| (edit_distance(&key, known), known))
+        .filter(|(distance, known)| *distance <= known.len().max(key.len()) / 3 + 1)
+        .min_by_key(|(distance, _)| *distance);
+
+    let message = match suggestion {
+        Some((_, known)) => format!("unknown account constraint `{key}`, did you mean `{known}`?"),
+        None => format!(
+            "unknown account constraint `{key}`, expected one of: {}",
+            CONSTRAINT_KEYS.join(", ")
+        ),
+    };
+    SynError::new_spanned(path, message)
+}
+
+// Levenshtein distance
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// Matches `namespace::name`, e.g. `realloc::payer`
+fn is_namespaced(path: &syn::Path, namespace: &str, name: &str) -> bool {
+    let mut segments = path.segments.iter();
+    matches!(
+        (segments.next(), segments.next(), segments.next()),
+        (Some(first), Some(second), None) if first.ident == namespace && second.ident == name
+    )
+}
+
+// `<value>` or `<value> @ <error>`
+fn parse_with_error<T: syn::parse::Parse>(input: ParseStream) -> Result<WithError<T>, SynError> {
+    let value = input.parse()?;
+    let error = if input.peek(Token![@]) {
+        input.parse::<Token![@]>()?;
+        Some(input.parse()?)
+    } else {
+        None
+    };
+    Ok(WithError { value, error })
+}
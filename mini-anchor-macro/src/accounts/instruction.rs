@@ -0,0 +1,37 @@
+use syn::{punctuated::Punctuated, Attribute, Error as SynError, Ident, Token, Type};
+
+// One `name: Type` entry of #[instruction(...)]
+pub(crate) struct InstructionArg {
+    pub name: Ident,
+    pub ty: Type,
+}
+
+impl syn::parse::Parse for InstructionArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        Ok(Self { name, ty })
+    }
+}
+
+// Leading handler arguments declared with #[instruction(...)] on the struct,
+// in the order they appear in the instruction data
+pub(crate) fn parse_instruction_args(attrs: &[Attribute]) -> Result<Vec<InstructionArg>, SynError> {
+    let mut attrs = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("instruction"));
+
+    let Some(attr) = attrs.next() else {
+        return Ok(Vec::new());
+    };
+    if let Some(duplicate) = attrs.next() {
+        return Err(SynError::new_spanned(
+            duplicate,
+            "#[instruction(...)] can only be given once",
+        ));
+    }
+
+    let args = attr.parse_args_with(Punctuated::<InstructionArg, Token![,]>::parse_terminated)?;
+    Ok(args.into_iter().collect())
+}
@@ -0,0 +1,1432 @@
+mod constraints;
+mod instruction;
+mod ty;
+
+use constraints::{
+    parse_constraints, Constraints, InitConstraint, ReallocConstraint, SeedsConstraint,
+};
+use instruction::parse_instruction_args;
+use proc_macro::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Error as SynError, Expr,
+    Field, Fields, Generics, Ident, Lifetime, Type,
+};
+use ty::{
+    bumps_generics, has_check_doc, is_boxed, is_optional, mentions_type_params,
+    unwrap_account_type, with_static_lifetime, with_static_lifetimes,
+};
+
+pub fn derive_accounts_impl(token: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(token as DeriveInput);
+
+    // Extract struct name and generics
+    let struct_name = &input.ident;
+    let generics = &input.generics;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Extract fields from the struct
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            fields => {
+                // Unit structs have nothing to point at but their name
+                let span = match fields {
+                    Fields::Unit => struct_name.span(),
+                    _ => fields.span(),
+                };
+                return SynError::new(
+                    span,
+                    "Accounts can only be derived for structs with named fields",
+                )
+                .to_compile_error()
+                .into();
+            }
+        },
+        Data::Enum(data) => {
+            return SynError::new(
+                data.enum_token.span(),
+                "Accounts can only be derived for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+        Data::Union(data) => {
+            return SynError::new(
+                data.union_token.span(),
+                "Accounts can only be derived for structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    // The first lifetime parameter is the one the `AccountInfo`s are borrowed for
+    let Some(lifetime) = generics.lifetimes().next().map(|lt| &lt.lifetime) else {
+        return SynError::new(
+            struct_name.span(),
+            "Accounts structs need a lifetime parameter for their accounts, e.g. `<'info>`",
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    let instruction_args = match parse_instruction_args(&input.attrs) {
+        Ok(args) => args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    // Parse #[account(...)] constraints for each field
+    let mut parsed_fields = Vec::new();
+    for field in fields.iter() {
+        match parse_constraints(field) {
+            Ok(constraints) => parsed_fields.push((field, constraints)),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+
+    if let Err(err) = validate_field_types(&parsed_fields) {
+        return err.to_compile_error().into();
+    }
+
+    if let Err(err) = validate_init(&parsed_fields, input.span()) {
+        return err.to_compile_error().into();
+    }
+
+    if let Err(err) = validate_has_one(&parsed_fields) {
+        return err.to_compile_error().into();
+    }
+
+    if let Err(err) = validate_close(&parsed_fields) {
+        return err.to_compile_error().into();
+    }
+
+    if let Err(err) = validate_realloc(&parsed_fields, input.span()) {
+        return err.to_compile_error().into();
+    }
+
+    if let Err(err) = validate_dup(&parsed_fields) {
+        return err.to_compile_error().into();
+    }
+
+    // Generate code for each field
+    let field_parsers = parsed_fields.iter().map(|(field, constraints)| {
+        let field_name = field.ident.as_ref().unwrap();
+        generate_field_parser(struct_name, field_name, &field.ty, constraints)
+    });
+
+    let check_doc_assertions = generate_check_doc_assertions(&parsed_fields, generics);
+    let nested_assertions = generate_nested_assertions(&parsed_fields, generics);
+
+    let payer_checks = generate_payer_checks(&parsed_fields);
+
+    let duplicate_checks = generate_duplicate_checks(struct_name, &parsed_fields);
+
+    let field_inits = parsed_fields.iter().map(|(field, constraints)| {
+        let field_name = field.ident.as_ref().unwrap();
+        generate_field_init(
+            struct_name,
+            field_name,
+            &field.ty,
+            constraints,
+            &parsed_fields,
+        )
+    });
+
+    let field_constraints = parsed_fields.iter().map(|(field, constraints)| {
+        let field_name = field.ident.as_ref().unwrap();
+        generate_field_constraints(
+            struct_name,
+            field_name,
+            &field.ty,
+            constraints,
+            &parsed_fields,
+        )
+    });
+
+    let field_exits = parsed_fields.iter().map(|(field, constraints)| {
+        let field_name = field.ident.as_ref().unwrap();
+        generate_field_exit(
+            struct_name,
+            field_name,
+            &field.ty,
+            constraints,
+            &parsed_fields,
+        )
+    });
+
+    let field_names: Vec<_> = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+
+    // Decoded up front so seeds, space and constraints can refer to them
+    let arg_decoders = instruction_args.iter().map(|arg| {
+        let name = &arg.name;
+        let ty = &arg.ty;
+        quote! {
+            let #name: #ty = {
+                let (value, read) = <#ty as ::mini_anchor::AnchorDeserialize>::deserialize(__ix_data)
+                    .map_err(|_| ::mini_anchor::error::ErrorCode::InstructionDidNotDeserialize)?;
+                __ix_data = &__ix_data[read..];
+                value
+            };
+        }
+    });
+
+    // One bump per PDA field, available to handlers through `ctx.bumps`, and the
+    // bumps of each field without constraints under its name. Those are the ones
+    // that can be nested structs, single accounts have `()`.
+    let mut bump_names = Vec::new();
+    let mut bump_types = Vec::new();
+    let mut nested_bumps = Vec::new();
+    for (field, constraints) in &parsed_fields {
+        if constraints.key_span.is_none() {
+            let nested_type = with_static_lifetime(&field.ty, lifetime);
+            bump_names.push(field.ident.as_ref().unwrap());
+            bump_types.push(quote! { <#nested_type as ::mini_anchor::Bumps>::Bumps });
+            nested_bumps.push(nested_type);
+        } else if constraints.seeds.is_some() {
+            bump_names.push(field.ident.as_ref().unwrap());
+            bump_types.push(quote! { u8 });
+        }
+    }
+
+    let vis = &input.vis;
+    let bumps_name = format_ident!("{}Bumps", struct_name);
+    let bumps_struct_name = bumps_name.to_string();
+
+    // The bumps struct has no lifetime, but takes the type and const parameters,
+    // which the bumps of nested structs can depend on
+    let mut bumps_generics = bumps_generics(generics);
+    let bumps_where = bumps_generics.make_where_clause();
+    for nested_type in &nested_bumps {
+        bumps_where
+            .predicates
+            .push(parse_quote! { #nested_type: ::mini_anchor::Bumps });
+    }
+    let (bumps_impl_generics, bumps_ty_generics, bumps_where) = bumps_generics.split_for_impl();
+    let bumps_where_with = |bound: proc_macro2::TokenStream| {
+        let mut where_clause = bumps_where
+            .cloned()
+            .unwrap_or_else(|| parse_quote! { where });
+        for nested_type in &nested_bumps {
+            where_clause
+                .predicates
+                .push(parse_quote! { <#nested_type as ::mini_anchor::Bumps>::Bumps: #bound });
+        }
+        where_clause
+    };
+    let default_where = bumps_where_with(quote! { ::core::default::Default });
+    let clone_where = bumps_where_with(quote! { ::core::clone::Clone });
+    let bumps_ty = quote! { #bumps_name #bumps_ty_generics };
+    let type_params: Vec<_> = generics.type_params().map(|param| &param.ident).collect();
+    let phantom_field = (!type_params.is_empty()).then(|| {
+        quote! { __phantom: ::core::marker::PhantomData<fn() -> (#(#type_params,)*)>, }
+    });
+    let phantom_value = phantom_field
+        .as_ref()
+        .map(|_| quote! { __phantom: ::core::marker::PhantomData, });
+
+    quote! {
+        #vis struct #bumps_name #bumps_impl_generics #bumps_where {
+            #(pub #bump_names: #bump_types,)*
+            #phantom_field
+        }
+
+        // Written out rather than derived, which would require the type
+        // parameters themselves to be `Default`, `Clone` and `Debug`
+        impl #bumps_impl_generics ::core::default::Default for #bumps_ty #default_where {
+            fn default() -> Self {
+                Self {
+                    #(#bump_names: ::core::default::Default::default(),)*
+                    #phantom_value
+                }
+            }
+        }
+
+        impl #bumps_impl_generics ::core::clone::Clone for #bumps_ty #clone_where {
+            fn clone(&self) -> Self {
+                Self {
+                    #(#bump_names: ::core::clone::Clone::clone(&self.#bump_names),)*
+                    #phantom_value
+                }
+            }
+        }
+
+        impl #bumps_impl_generics ::core::fmt::Debug for #bumps_ty #bumps_where {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(#bumps_struct_name)
+                    #(.field(::core::stringify!(#bump_names), &self.#bump_names))*
+                    .finish()
+            }
+        }
+
+        impl #impl_generics ::mini_anchor::Bumps for #struct_name #ty_generics #where_clause {
+            type Bumps = #bumps_ty;
+        }
+
+        impl #impl_generics ::mini_anchor::Accounts<#lifetime> for #struct_name #ty_generics #where_clause {
+            const NESTED: bool = true;
+
+            #[allow(unused_variables)]
+            fn try_accounts(
+                program_id: &::mini_anchor::solana_program::pubkey::Pubkey,
+                accounts: &mut &#lifetime [::mini_anchor::solana_program::account_info::AccountInfo<#lifetime>],
+                ix_data: &[u8],
+                bumps: &mut #bumps_ty,
+            ) -> Result<Self, ::mini_anchor::solana_program::program_error::ProgramError> {
+                #[allow(unused_mut)]
+                let mut __ix_data = ix_data;
+                #check_doc_assertions
+                #nested_assertions
+                #payer_checks
+                #(#arg_decoders)*
+
+                #(#field_parsers)*
+
+                #duplicate_checks
+
+                #(#field_inits)*
+
+                #(#field_constraints)*
+
+                Ok(Self {
+                    #(#field_names), *
+                })
+            }
+        }
+
+        impl #impl_generics ::mini_anchor::ToAccountInfos<#lifetime> for #struct_name #ty_generics #where_clause {
+            fn to_account_infos(
+                &self,
+            ) -> Vec<::mini_anchor::solana_program::account_info::AccountInfo<#lifetime>> {
+                let mut account_infos = Vec::new();
+                #(
+                    account_infos.extend(::mini_anchor::ToAccountInfos::to_account_infos(&self.#field_names));
+                )*
+                account_infos
+            }
+        }
+
+        impl #impl_generics ::mini_anchor::AccountsExit<#lifetime> for #struct_name #ty_generics #where_clause {
+            fn exit(
+                &self,
+                program_id: &::mini_anchor::solana_program::pubkey::Pubkey,
+            ) -> Result<(), ::mini_anchor::solana_program::program_error::ProgramError> {
+                #(#field_exits)*
+                Ok(())
+            }
+        }
+    }
+    .into()
+}
+
+fn generate_field_parser(
+    struct_name: &Ident,
+    field_name: &Ident,
+    field_type: &Type,
+    constraints: &Constraints,
+) -> proc_macro2::TokenStream {
+    let account_path = format!("{}.{}", struct_name, field_name);
+    let flag_checks = generate_flag_checks(struct_name, field_name, constraints);
+
+    // Accounts being initialized hold no data yet, they are built once every field is parsed
+    if constraints.init.is_some() || constraints.zero {
+        let info_name = init_info_ident(field_name);
+        return quote! {
+            let #info_name = {
+                let (info, rest) = (*accounts).split_first().ok_or_else(|| {
+                    ::mini_anchor::error::with_account(
+                        #account_path,
+                        ::mini_anchor::error::ErrorCode::AccountNotEnoughKeys.into(),
+                    )
+                })?;
+                #flag_checks
+                *accounts = rest;
+                info
+            };
+        };
+    }
+
+    // Checked on the account about to be loaded, unless it is the program id
+    // standing in for an absent optional account
+    let flag_checks = if flag_checks.is_empty() {
+        quote! {}
+    } else if is_optional(field_type) {
+        quote! {
+            if let Some(info) = accounts.first() {
+                if info.key != program_id {
+                    #flag_checks
+                }
+            }
+        }
+    } else {
+        quote! {
+            if let Some(info) = accounts.first() {
+                #flag_checks
+            }
+        }
+    };
+
+    // Every field type loads itself through its `Accounts` impl. Fields that can be
+    // nested structs record their bumps, account types have none to record.
+    let bumps = if constraints.key_span.is_none() {
+        quote! { &mut bumps.#field_name }
+    } else {
+        quote! { &mut ::core::default::Default::default() }
+    };
+
+    quote! {
+        #flag_checks
+        let #field_name: #field_type = <#field_type as ::mini_anchor::Accounts>::try_accounts(
+            program_id,
+            accounts,
+            ix_data,
+            #bumps,
+        )
+        .map_err(|error| ::mini_anchor::error::with_account(#account_path, error))?;
+    }
+}
+
+// `mut` and `signer`, checked on `info` before the account is loaded
+fn generate_flag_checks(
+    struct_name: &Ident,
+    field_name: &Ident,
+    constraints: &Constraints,
+) -> proc_macro2::TokenStream {
+    let mut_check = constraints.is_mut.then(|| {
+        let log = violation_log(
+            struct_name,
+            field_name,
+            "mut",
+            "account is not writable",
+            None,
+        );
+        quote! {
+            if !info.is_writable {
+                #log
+                return Err(::mini_anchor::error::ErrorCode::ConstraintMut.into());
+            }
+        }
+    });
+
+    let signer_check = constraints.is_signer.then(|| {
+        let log = violation_log(
+            struct_name,
+            field_name,
+            "signer",
+            "account did not sign",
+            None,
+        );
+        quote! {
+            if !info.is_signer {
+                #log
+                return Err(::mini_anchor::error::ErrorCode::ConstraintSigner.into());
+            }
+        }
+    });
+
+    quote! {
+        #mut_check
+        #signer_check
+    }
+}
+
+// Logs the `Struct.field` and constraint behind a failed check, then the
+// expected and actual values when the check compared two
+fn violation_log(
+    struct_name: &Ident,
+    field_name: &Ident,
+    constraint: &str,
+    detail: &str,
+    values: Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)>,
+) -> proc_macro2::TokenStream {
+    let log = format!(
+        "Constraint {} violated by `{}.{}`: {}",
+        constraint, struct_name, field_name, detail
+    );
+    let values = values.map(|(expected, actual)| {
+        quote! { ::mini_anchor::error::log_mismatch(#expected, #actual); }
+    });
+    quote! {
+        ::mini_anchor::solana_program::msg!(#log);
+        #values
+    }
+}
+
+// `Box::new(value)` for boxed fields, keeping large accounts off the stack
+fn wrap_boxed(field_type: &Type, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    if is_boxed(field_type) {
+        quote! { ::std::boxed::Box::new(#value) }
+    } else {
+        value
+    }
+}
+
+// Two slots holding the same writable account would both be written back on exit,
+// so a mutable field's key must differ from every other mutable field, and from
+// accounts keeping their own copy of the data. Nested structs check their own fields.
+fn generate_duplicate_checks(
+    struct_name: &Ident,
+    fields: &[(&Field, Constraints)],
+) -> proc_macro2::TokenStream {
+    let mut checks = Vec::new();
+    for (index, (field, constraints)) in fields.iter().enumerate() {
+        for (other, other_constraints) in &fields[index + 1..] {
+            // A read-only account only conflicts with a mutable one if it holds data
+            let read_only_ty = match (constraints.is_mut, other_constraints.is_mut) {
+                (false, false) => continue,
+                (true, true) => None,
+                (true, false) => Some(&other.ty),
+                (false, true) => Some(&field.ty),
+            };
+
+            let name = field.ident.as_ref().unwrap();
+            let other_name = other.ident.as_ref().unwrap();
+            if constraints.dup.contains(other_name) || other_constraints.dup.contains(name) {
+                continue;
+            }
+
+            let key = field_key(name, &field.ty, constraints);
+            let other_key = field_key(other_name, &other.ty, other_constraints);
+            let detail = format!(
+                "`{}.{}` has the same key, add `dup = {}` if that is intended",
+                struct_name, other_name, other_name
+            );
+            let log = violation_log(struct_name, name, "dup", &detail, None);
+            let check = quote! {
+                if let (Some(__key), Some(__other_key)) = (#key, #other_key) {
+                    if __key == __other_key {
+                        #log
+                        ::mini_anchor::solana_program::msg!("Key: {}", __key);
+                        return Err(::mini_anchor::error::ErrorCode::ConstraintDuplicateMutableAccount.into());
+                    }
+                }
+            };
+            checks.push(match read_only_ty {
+                Some(ty) => quote! {
+                    if <#ty as ::mini_anchor::Accounts>::HOLDS_DATA {
+                        #check
+                    }
+                },
+                None => check,
+            });
+        }
+    }
+
+    quote! { #(#checks)* }
+}
+
+// `Option<&Pubkey>` of a parsed field, `None` for an optional account that was not passed
+fn field_key(
+    field_name: &Ident,
+    field_type: &Type,
+    constraints: &Constraints,
+) -> proc_macro2::TokenStream {
+    if constraints.init.is_some() || constraints.zero {
+        let info_name = init_info_ident(field_name);
+        return quote! { Some(#info_name.key) };
+    }
+
+    // A field without constraints can be a nested struct, which has no key of its
+    // own. It is only compared when its type holds data, making it a single account.
+    if constraints.key_span.is_none() {
+        return quote! {
+            ::mini_anchor::ToAccountInfos::to_account_infos(&#field_name)
+                .first()
+                .map(|info| info.key)
+        };
+    }
+
+    let info = field_info(quote! { #field_name }, field_type);
+    if is_optional(field_type) {
+        quote! { #field_name.as_ref().map(|#field_name| #info.key) }
+    } else {
+        quote! { Some(#info.key) }
+    }
+}
+
+// Creates or loads `init` and `zero` accounts, after every field has been parsed
+// and before any constraint runs, so constraints can refer to them wherever they
+// are declared
+fn generate_field_init(
+    struct_name: &Ident,
+    field_name: &Ident,
+    field_type: &Type,
+    constraints: &Constraints,
+    fields: &[(&Field, Constraints)],
+) -> proc_macro2::TokenStream {
+    if let Some(init) = &constraints.init {
+        generate_init(
+            struct_name,
+            field_name,
+            field_type,
+            init,
+            constraints.seeds.as_ref(),
+            fields,
+        )
+    } else if constraints.zero {
+        generate_zero(
+            struct_name,
+            field_name,
+            field_type,
+            constraints.seeds.as_ref(),
+        )
+    } else {
+        quote! {}
+    }
+}
+
+// Runs after every field has been parsed and initialized, so constraints can
+// refer to any of them
+fn generate_field_constraints(
+    struct_name: &Ident,
+    field_name: &Ident,
+    field_type: &Type,
+    constraints: &Constraints,
+    fields: &[(&Field, Constraints)],
+) -> proc_macro2::TokenStream {
+    let info = field_info(quote! { #field_name }, field_type);
+
+    // Seeds of `init` and `zero` accounts are checked before they are created
+    let seeds_check = if constraints.init.is_some() || constraints.zero {
+        quote! {}
+    } else if let Some(seeds) = &constraints.seeds {
+        let seeds_check = generate_seeds_check(
+            struct_name,
+            field_name,
+            &quote! { #info.key },
+            seeds,
+            quote! {},
+        );
+        quote! {
+            #seeds_check;
+        }
+    } else {
+        quote! {}
+    };
+
+    let has_one_checks = constraints.has_one.iter().map(|has_one| {
+        let target = &has_one.value;
+        let (target_field, _) =
+            find_field(fields, target).expect("has_one target is checked by validate_has_one");
+        let target_info = field_info(quote! { #target }, &target_field.ty);
+        let detail = format!(
+            "`{}.{}` does not match the key of `{}`",
+            field_name, target, target
+        );
+        let log = violation_log(
+            struct_name,
+            field_name,
+            "has_one",
+            &detail,
+            Some((quote! { &#field_name.#target }, quote! { #target_info.key })),
+        );
+        let error = constraint_error(&has_one.error, quote! { ConstraintHasOne });
+        quote! {
+            if &#field_name.#target != #target_info.key {
+                #log
+                return Err(#error.into());
+            }
+        }
+    });
+
+    let address_check = constraints.address.as_ref().map(|address| {
+        let expected = &address.value;
+        let log = violation_log(
+            struct_name,
+            field_name,
+            "address",
+            "unexpected key",
+            Some((quote! { __expected }, quote! { #info.key })),
+        );
+        let error = constraint_error(&address.error, quote! { ConstraintAddress });
+        quote! {
+            {
+                let __expected: ::mini_anchor::solana_program::pubkey::Pubkey = #expected;
+                if *#info.key != __expected {
+                    #log
+                    return Err(#error.into());
+                }
+            }
+        }
+    });
+
+    let owner_check = constraints.owner.as_ref().map(|owner| {
+        let expected = &owner.value;
+        let log = violation_log(
+            struct_name,
+            field_name,
+            "owner",
+            "unexpected owner",
+            Some((quote! { __expected }, quote! { #info.owner })),
+        );
+        let error = constraint_error(&owner.error, quote! { ConstraintOwner });
+        quote! {
+            {
+                let __expected: ::mini_anchor::solana_program::pubkey::Pubkey = #expected;
+                if *#info.owner != __expected {
+                    #log
+                    return Err(#error.into());
+                }
+            }
+        }
+    });
+
+    // Closing into itself would leave the lamports in the closed account
+    let close_check = constraints.close.as_ref().map(|destination| {
+        let (destination_field, _) = find_field(fields, destination)
+            .expect("close destination is checked by validate_close");
+        let destination_info = field_info(quote! { #destination }, &destination_field.ty);
+        let detail = format!("cannot be closed into `{}`, the same account", destination);
+        let log = violation_log(struct_name, field_name, "close", &detail, None);
+        quote! {
+            if #info.key == #destination_info.key {
+                #log
+                return Err(::mini_anchor::error::ErrorCode::ConstraintClose.into());
+            }
+        }
+    });
+
+    let raw_checks = constraints.raw.iter().map(|raw| {
+        let condition = &raw.value;
+        let detail = format!("`{}` is false", quote! { #condition });
+        let log = violation_log(struct_name, field_name, "raw", &detail, None);
+        let error = constraint_error(&raw.error, quote! { ConstraintRaw });
+        quote! {
+            if !(#condition) {
+                #log
+                return Err(#error.into());
+            }
+        }
+    });
+
+    let realloc = constraints
+        .realloc
+        .as_ref()
+        .map(|realloc| generate_realloc(struct_name, field_name, &info, realloc, fields));
+
+    let checks = quote! {
+        #seeds_check
+        #(#has_one_checks)*
+        #address_check
+        #owner_check
+        #close_check
+        #(#raw_checks)*
+        #realloc
+    };
+
+    // Constraints on an optional account only apply when it was passed
+    if is_optional(field_type) && !checks.is_empty() {
+        quote! {
+            if let Some(#field_name) = &#field_name {
+                #checks
+            }
+        }
+    } else {
+        checks
+    }
+}
+
+// Resize the account before the handler runs, keeping it exactly rent-exempt
+fn generate_realloc(
+    struct_name: &Ident,
+    field_name: &Ident,
+    info: &proc_macro2::TokenStream,
+    realloc: &ReallocConstraint,
+    fields: &[(&Field, Constraints)],
+) -> proc_macro2::TokenStream {
+    let space = &realloc.space;
+    let payer_info = payer_info(fields, &realloc.payer);
+    let system_program_info = system_program_info(fields);
+    let zero = match &realloc.zero {
+        Some(zero) => quote! { #zero },
+        None => quote! { false },
+    };
+    let log = violation_log(
+        struct_name,
+        field_name,
+        "realloc",
+        "cannot grow by more than MAX_PERMITTED_DATA_INCREASE in one instruction",
+        Some((
+            quote! { __old_space + ::mini_anchor::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE },
+            quote! { __new_space },
+        )),
+    );
+
+    quote! {
+        {
+            let __info: &::mini_anchor::solana_program::account_info::AccountInfo = #info;
+            let __new_space: usize = #space;
+            let __old_space = __info.data_len();
+
+            // An unchanged size leaves the account, and any lamports it holds
+            // above rent-exempt, as it is
+            if __new_space != __old_space {
+                if __new_space.saturating_sub(__old_space)
+                    > ::mini_anchor::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE
+                {
+                    #log
+                    return Err(::mini_anchor::error::ErrorCode::AccountReallocExceedsLimit.into());
+                }
+
+                let __rent_minimum = <::mini_anchor::solana_program::rent::Rent as ::mini_anchor::solana_program::sysvar::Sysvar>::get()?
+                    .minimum_balance(__new_space);
+                let __lamports = __info.lamports();
+
+                if __new_space > __old_space {
+                    if __rent_minimum > __lamports {
+                        ::mini_anchor::system_program::transfer(
+                            #payer_info,
+                            __info,
+                            __rent_minimum - __lamports,
+                            #system_program_info,
+                        )?;
+                    }
+                } else if __lamports > __rent_minimum {
+                    // The program owns the account, so it can hand the rent freed by
+                    // shrinking back to the payer itself
+                    let __refund = __lamports - __rent_minimum;
+                    **__info.try_borrow_mut_lamports()? -= __refund;
+                    **#payer_info.try_borrow_mut_lamports()? += __refund;
+                }
+
+                #[allow(deprecated)]
+                __info.realloc(__new_space, #zero)?;
+            }
+        }
+    }
+}
+
+// The `@ <error>` given on the constraint, or the framework's own error
+fn constraint_error(
+    custom: &Option<Expr>,
+    default: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match custom {
+        Some(error) => quote! { #error },
+        None => quote! { ::mini_anchor::error::ErrorCode::#default },
+    }
+}
+
+fn generate_init(
+    struct_name: &Ident,
+    field_name: &Ident,
+    field_type: &Type,
+    init: &InitConstraint,
+    seeds: Option<&SeedsConstraint>,
+    fields: &[(&Field, Constraints)],
+) -> proc_macro2::TokenStream {
+    let info_name = init_info_ident(field_name);
+    let payer_info = payer_info(fields, &init.payer);
+    let system_program_info = system_program_info(fields);
+    let space = match &init.space {
+        Some(space) => quote! { #space },
+        // The space of the account type the field derefs to, e.g. `T` of `Account<'info, T>`
+        None => {
+            let account_type = unwrap_account_type(field_type);
+            quote! {
+                <<#account_type as ::core::ops::Deref>::Target as ::mini_anchor::Space>::SPACE
+            }
+        }
+    };
+
+    // A PDA has no private key, the program signs for it with its seeds
+    let signer_seeds = match seeds {
+        Some(_) => quote! { &[&__signer_seeds[..]] },
+        None => quote! { &[] },
+    };
+
+    let create = quote! {
+        ::mini_anchor::system_program::create_account(
+            #payer_info,
+            #info_name,
+            space,
+            program_id,
+            #system_program_info,
+            #signer_seeds,
+        )?;
+        ::mini_anchor::Account::try_init(#info_name)?
+    };
+
+    let build = if init.if_needed {
+        let log = violation_log(
+            struct_name,
+            field_name,
+            "space",
+            "existing account does not have the expected size",
+            Some((quote! { space }, quote! { #info_name.data_len() })),
+        );
+        quote! {
+            // Only addresses still owned by the System Program are created
+            if ::mini_anchor::system_program::check_id(#info_name.owner) {
+                #create
+            } else {
+                // Loading checks the owner and discriminator, so an account of another
+                // type or one that was never initialized cannot be taken over
+                let account = ::mini_anchor::Account::try_from(#info_name)?;
+                if #info_name.data_len() != space {
+                    #log
+                    return Err(::mini_anchor::error::ErrorCode::ConstraintSpace.into());
+                }
+                account
+            }
+        }
+    } else {
+        create
+    };
+
+    let build = wrap_boxed(field_type, quote! { { #build } });
+    let build = quote! {
+        let space: usize = #space;
+        #build
+    };
+    let build = match seeds {
+        Some(seeds) => generate_seeds_check(
+            struct_name,
+            field_name,
+            &quote! { #info_name.key },
+            seeds,
+            build,
+        ),
+        None => build,
+    };
+
+    quote! {
+        let #field_name: #field_type = {
+            #build
+        };
+    }
+}
+
+// Accounts created client-side and assigned to the program, with an unset discriminator
+fn generate_zero(
+    struct_name: &Ident,
+    field_name: &Ident,
+    field_type: &Type,
+    seeds: Option<&SeedsConstraint>,
+) -> proc_macro2::TokenStream {
+    let info_name = init_info_ident(field_name);
+    let zero_log = violation_log(
+        struct_name,
+        field_name,
+        "zero",
+        "account is already initialized",
+        None,
+    );
+    let rent_log = violation_log(
+        struct_name,
+        field_name,
+        "rent_exempt",
+        "account is not rent exempt",
+        Some((
+            quote! { rent.minimum_balance(#info_name.data_len()) },
+            quote! { #info_name.lamports() },
+        )),
+    );
+    let init = wrap_boxed(
+        field_type,
+        quote! { ::mini_anchor::Account::try_init(#info_name)? },
+    );
+
+    let build = quote! {
+        {
+            let data = #info_name.try_borrow_data()?;
+            if data.len() >= 8 && data[..8] != [0u8; 8] {
+                #zero_log
+                return Err(::mini_anchor::error::ErrorCode::ConstraintZero.into());
+            }
+        }
+
+        let rent = <::mini_anchor::solana_program::rent::Rent as ::mini_anchor::solana_program::sysvar::Sysvar>::get()?;
+        if !rent.is_exempt(#info_name.lamports(), #info_name.data_len()) {
+            #rent_log
+            return Err(::mini_anchor::error::ErrorCode::ConstraintRentExempt.into());
+        }
+
+        #init
+    };
+    let build = match seeds {
+        Some(seeds) => generate_seeds_check(
+            struct_name,
+            field_name,
+            &quote! { #info_name.key },
+            seeds,
+            build,
+        ),
+        None => build,
+    };
+
+    quote! {
+        let #field_name: #field_type = {
+            #build
+        };
+    }
+}
+
+// Derives the PDA from its seeds, checks it against `key` and records the bump,
+// then evaluates to `body`, which can use `__signer_seeds` (seeds followed by the bump).
+fn generate_seeds_check(
+    struct_name: &Ident,
+    field_name: &Ident,
+    key: &proc_macro2::TokenStream,
+    seeds: &SeedsConstraint,
+    body: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let seed_exprs = &seeds.seeds;
+    let bump_log = violation_log(
+        struct_name,
+        field_name,
+        "seeds",
+        "seeds and bump do not derive a valid program address",
+        None,
+    );
+    let key_log = violation_log(
+        struct_name,
+        field_name,
+        "seeds",
+        "key does not match the address derived from the seeds",
+        Some((quote! { __expected_key }, quote! { #key })),
+    );
+
+    let derive_address = match &seeds.bump {
+        // Canonical bump: the first one (from 255 down) that is off the curve
+        None => quote! {
+            let (__expected_key, __bump) =
+                ::mini_anchor::solana_program::pubkey::Pubkey::find_program_address(__seeds, program_id);
+            let __bump_bytes = [__bump];
+            let mut __signer_seeds: Vec<&[u8]> = __seeds.to_vec();
+            __signer_seeds.push(&__bump_bytes);
+        },
+        // Caller-provided bump, usually one stored in the account itself
+        Some(bump) => quote! {
+            let __bump: u8 = #bump;
+            let __bump_bytes = [__bump];
+            let mut __signer_seeds: Vec<&[u8]> = __seeds.to_vec();
+            __signer_seeds.push(&__bump_bytes);
+            let __expected_key =
+                ::mini_anchor::solana_program::pubkey::Pubkey::create_program_address(&__signer_seeds, program_id)
+                    .map_err(|_| {
+                        #bump_log
+                        ::mini_anchor::error::ErrorCode::ConstraintSeeds
+                    })?;
+        },
+    };
+
+    // Temporaries in a match scrutinee live until the end of the match, so seeds
+    // such as `authority.key().as_ref()` stay borrowed for the whole body
+    quote! {
+        match ::core::convert::identity::<&[&[u8]]>(&[#(#seed_exprs),*]) {
+            __seeds => {
+                #derive_address
+                if #key != &__expected_key {
+                    #key_log
+                    return Err(::mini_anchor::error::ErrorCode::ConstraintSeeds.into());
+                }
+                bumps.#field_name = __bump;
+                #body
+            }
+        }
+    }
+}
+
+fn generate_field_exit(
+    struct_name: &Ident,
+    field_name: &Ident,
+    field_type: &Type,
+    constraints: &Constraints,
+    fields: &[(&Field, Constraints)],
+) -> proc_macro2::TokenStream {
+    let account_path = format!("{}.{}", struct_name, field_name);
+    let with_account = quote! {
+        .map_err(|error| ::mini_anchor::error::with_account(#account_path, error))?
+    };
+
+    // A nested struct runs the exit logic of each of its own fields, a single
+    // account without constraints is read-only
+    if constraints.key_span.is_none() {
+        return quote! {
+            if <#field_type as ::mini_anchor::Accounts>::NESTED {
+                ::mini_anchor::AccountsExit::exit(&self.#field_name, program_id)#with_account;
+            } else {
+                ::mini_anchor::AccountsExit::exit_read_only(&self.#field_name)#with_account;
+            }
+        };
+    }
+
+    let account = if is_optional(field_type) {
+        quote! { #field_name }
+    } else {
+        quote! { &self.#field_name }
+    };
+
+    // A closed account has nothing left to write back
+    let exit = if let Some(destination) = &constraints.close {
+        let (destination_field, _) = find_field(fields, destination)
+            .expect("close destination is checked by validate_close");
+        let destination_info = field_info(quote! { self.#destination }, &destination_field.ty);
+        quote! {
+            ::mini_anchor::AccountsClose::close(#account, #destination_info)#with_account;
+        }
+    } else if constraints.is_mut {
+        quote! {
+            ::mini_anchor::AccountsExit::exit(#account, program_id)#with_account;
+        }
+    } else {
+        quote! {
+            ::mini_anchor::AccountsExit::exit_read_only(#account)#with_account;
+        }
+    };
+
+    if is_optional(field_type) {
+        quote! {
+            if let Some(#field_name) = &self.#field_name {
+                #exit
+            }
+        }
+    } else {
+        exit
+    }
+}
+
+// Every field is loaded through its type's `Accounts` impl
+fn validate_field_types(fields: &[(&Field, Constraints)]) -> Result<(), SynError> {
+    for (field, _) in fields {
+        if !matches!(field.ty, Type::Path(_)) {
+            return Err(SynError::new_spanned(
+                &field.ty,
+                "unsupported account type, expected a type implementing `Accounts`",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// Checked at compile time: init creates a typed account through the System Program
+fn validate_init(
+    fields: &[(&Field, Constraints)],
+    struct_span: proc_macro2::Span,
+) -> Result<(), SynError> {
+    let mut needs_system_program = false;
+
+    for (field, constraints) in fields {
+        // There is no account to create when the slot holds `None`
+        if (constraints.init.is_some() || constraints.zero) && is_optional(&field.ty) {
+            return Err(SynError::new_spanned(
+                &field.ty,
+                "init and zero cannot be used on optional accounts",
+            ));
+        }
+
+        let Some(init) = &constraints.init else {
+            continue;
+        };
+        needs_system_program = true;
+
+        validate_payer(fields, &init.payer, "init")?;
+    }
+
+    if needs_system_program && !has_field(fields, "system_program") {
+        return Err(SynError::new(
+            struct_span,
+            "init requires a `system_program: Program<'info, System>` field",
+        ));
+    }
+
+    Ok(())
+}
+
+// Growing an account is paid for by a signer through the System Program
+fn validate_realloc(
+    fields: &[(&Field, Constraints)],
+    struct_span: proc_macro2::Span,
+) -> Result<(), SynError> {
+    let mut needs_system_program = false;
+
+    for (_, constraints) in fields {
+        let Some(realloc) = &constraints.realloc else {
+            continue;
+        };
+        needs_system_program = true;
+
+        if !constraints.is_mut {
+            return Err(SynError::new(
+                realloc.space.span(),
+                "realloc requires the account to be marked #[account(mut)]",
+            ));
+        }
+
+        validate_payer(fields, &realloc.payer, "realloc")?;
+    }
+
+    if needs_system_program && !has_field(fields, "system_program") {
+        return Err(SynError::new(
+            struct_span,
+            "realloc requires a `system_program: Program<'info, System>` field",
+        ));
+    }
+
+    Ok(())
+}
+
+// The payer has to sign and have its lamports debited
+fn validate_payer(
+    fields: &[(&Field, Constraints)],
+    payer: &Ident,
+    constraint: &str,
+) -> Result<(), SynError> {
+    let (_, payer_constraints) = find_required_field(fields, payer, "payer")?;
+
+    if !payer_constraints.is_mut {
+        return Err(SynError::new(
+            payer.span(),
+            format!(
+                "{} payer `{}` must be marked #[account(mut)]",
+                constraint, payer
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+// Nothing about unchecked account types is validated, so their fields have to say
+// why that is safe. Which types those are is up to their `Accounts` impl.
+fn generate_check_doc_assertions(
+    fields: &[(&Field, Constraints)],
+    generics: &Generics,
+) -> proc_macro2::TokenStream {
+    let assertions = fields
+        .iter()
+        .filter(|(field, _)| !has_check_doc(field))
+        .map(|(field, _)| {
+            let name = field.ident.as_ref().unwrap();
+            let message = format!(
+                "`{}` is not validated, add a `/// CHECK:` doc comment explaining why it is safe",
+                name
+            );
+            const_assert(
+                &field.ty,
+                generics,
+                |ty, lifetime| quote! { !<#ty as ::mini_anchor::Accounts<#lifetime>>::UNCHECKED },
+                &message,
+                name.span(),
+            )
+        });
+
+    quote! { #(#assertions)* }
+}
+
+// Whether a field is a nested struct is up to its type's `Accounts` impl. Those
+// check their own fields, so they take no constraints, and take up a variable
+// number of slots, so they cannot be optional.
+fn generate_nested_assertions(
+    fields: &[(&Field, Constraints)],
+    generics: &Generics,
+) -> proc_macro2::TokenStream {
+    let assertions = fields.iter().filter_map(|(field, constraints)| {
+        let (message, span) = match constraints.key_span {
+            Some(span) => (
+                "constraints cannot be applied to a nested Accounts struct",
+                span,
+            ),
+            None if is_optional(&field.ty) => (
+                "nested Accounts structs cannot be optional",
+                field.ident.as_ref().unwrap().span(),
+            ),
+            None => return None,
+        };
+        Some(const_assert(
+            &field.ty,
+            generics,
+            |ty, lifetime| quote! { !<#ty as ::mini_anchor::Accounts<#lifetime>>::NESTED },
+            message,
+            span,
+        ))
+    });
+
+    quote! { #(#assertions)* }
+}
+
+// Fails to compile with `message` unless `condition`, built from the field type
+// and the lifetime to name it with, holds. A type mentioning the struct's type or
+// const parameters can only be checked once the struct is used with concrete ones.
+fn const_assert(
+    ty: &Type,
+    generics: &Generics,
+    condition: impl FnOnce(&Type, &Lifetime) -> proc_macro2::TokenStream,
+    message: &str,
+    span: proc_macro2::Span,
+) -> proc_macro2::TokenStream {
+    if mentions_type_params(ty, generics) {
+        let lifetime = &generics.lifetimes().next().unwrap().lifetime;
+        let condition = condition(ty, lifetime);
+        quote_spanned! {span=>
+            const { ::core::assert!(#condition, #message) };
+        }
+    } else {
+        let ty = with_static_lifetimes(ty, generics);
+        let condition = condition(&ty, &parse_quote! { 'static });
+        quote_spanned! {span=>
+            const _: () = ::core::assert!(#condition, #message);
+        }
+    }
+}
+
+// Whether the payer signs is up to its type, so that part of validate_payer is left
+// to the compiler
+fn generate_payer_checks(fields: &[(&Field, Constraints)]) -> proc_macro2::TokenStream {
+    let payers = fields.iter().flat_map(|(_, constraints)| {
+        let init_payer = constraints.init.as_ref().map(|init| &init.payer);
+        let realloc_payer = constraints.realloc.as_ref().map(|realloc| &realloc.payer);
+        init_payer.into_iter().chain(realloc_payer)
+    });
+
+    let checks = payers.map(|payer| {
+        let (payer_field, _) =
+            find_field(fields, payer).expect("payer is checked by validate_payer");
+        let payer_ty = &payer_field.ty;
+        quote_spanned! {payer_ty.span()=>
+            {
+                fn __assert_signer<T: ::mini_anchor::IsSigner>() {}
+                __assert_signer::<#payer_ty>();
+            }
+        }
+    });
+
+    quote! { #(#checks)* }
+}
+
+// `&AccountInfo` behind an already parsed field, `field` being e.g. `counter` or
+// `self.counter`, or a reference to the account inside an optional field
+fn field_info(field: proc_macro2::TokenStream, field_type: &Type) -> proc_macro2::TokenStream {
+    // `Box<T>` is only `AsRef<T>`, so boxed accounts are dereferenced first
+    let account = match (is_boxed(field_type), is_optional(field_type)) {
+        (true, true) => quote! { &**#field },
+        (true, false) => quote! { &*#field },
+        (false, _) => quote! { &#field },
+    };
+    quote! {
+        ::core::convert::AsRef::<::mini_anchor::solana_program::account_info::AccountInfo>::as_ref(#account)
+    }
+}
+
+// has_one compares against a sibling field, so it has to exist
+fn validate_has_one(fields: &[(&Field, Constraints)]) -> Result<(), SynError> {
+    for (_, constraints) in fields {
+        for target in constraints.has_one.iter().map(|has_one| &has_one.value) {
+            find_required_field(fields, target, "has_one target")?;
+        }
+    }
+
+    Ok(())
+}
+
+// Closing moves lamports out of the account and into the destination
+fn validate_close(fields: &[(&Field, Constraints)]) -> Result<(), SynError> {
+    for (_, constraints) in fields {
+        let Some(destination) = &constraints.close else {
+            continue;
+        };
+
+        if !constraints.is_mut {
+            return Err(SynError::new(
+                destination.span(),
+                "close requires the account to be marked #[account(mut)]",
+            ));
+        }
+
+        let (_, destination_constraints) =
+            find_required_field(fields, destination, "close destination")?;
+
+        if !destination_constraints.is_mut {
+            return Err(SynError::new(
+                destination.span(),
+                format!(
+                    "close destination `{}` must be marked #[account(mut)]",
+                    destination
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// dup names the sibling fields allowed to share the account's key
+fn validate_dup(fields: &[(&Field, Constraints)]) -> Result<(), SynError> {
+    for (_, constraints) in fields {
+        for other in &constraints.dup {
+            if find_field(fields, other).is_none() {
+                return Err(SynError::new(
+                    other.span(),
+                    format!("dup target `{}` is not a field of this struct", other),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn find_field<'a, 'f>(
+    fields: &'a [(&'f Field, Constraints)],
+    name: &Ident,
+) -> Option<&'a (&'f Field, Constraints)> {
+    fields
+        .iter()
+        .find(|(field, _)| field.ident.as_ref() == Some(name))
+}
+
+// A field that other constraints read from, so it must always be present
+fn find_required_field<'a, 'f>(
+    fields: &'a [(&'f Field, Constraints)],
+    name: &Ident,
+    role: &str,
+) -> Result<&'a (&'f Field, Constraints), SynError> {
+    let Some(field) = find_field(fields, name) else {
+        return Err(SynError::new(
+            name.span(),
+            format!("{} `{}` is not a field of this struct", role, name),
+        ));
+    };
+
+    if is_optional(&field.0.ty) {
+        return Err(SynError::new(
+            name.span(),
+            format!("{} `{}` cannot be an optional account", role, name),
+        ));
+    }
+
+    Ok(field)
+}
+
+// `&AccountInfo` of an init or realloc payer, checked to exist by validate_payer
+fn payer_info(fields: &[(&Field, Constraints)], payer: &Ident) -> proc_macro2::TokenStream {
+    let (payer_field, _) = find_field(fields, payer).expect("payer is checked by validate_payer");
+    field_info(quote! { #payer }, &payer_field.ty)
+}
+
+// `&AccountInfo` of the System Program, which validate_init and validate_realloc require
+fn system_program_info(fields: &[(&Field, Constraints)]) -> proc_macro2::TokenStream {
+    let (field, _) = fields
+        .iter()
+        .find(|(field, _)| {
+            field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| ident == "system_program")
+        })
+        .expect("system_program is checked by validate_init and validate_realloc");
+    field_info(quote! { system_program }, &field.ty)
+}
+
+fn has_field(fields: &[(&Field, Constraints)], name: &str) -> bool {
+    fields
+        .iter()
+        .any(|(field, _)| field.ident.as_ref().is_some_and(|ident| ident == name))
+}
+
+fn init_info_ident(field_name: &Ident) -> Ident {
+    format_ident!("__{}_info", field_name)
+}
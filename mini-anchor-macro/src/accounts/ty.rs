@@ -0,0 +1,198 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::punctuated::Punctuated;
+use syn::visit_mut::VisitMut;
+use syn::{
+    Expr, Field, GenericArgument, GenericParam, Generics, Ident, Lifetime, Lit, Meta,
+    PathArguments, Token, Type, TypeParamBound, WherePredicate,
+};
+
+// True if one of the field's doc lines starts with `CHECK:`
+pub(crate) fn has_check_doc(field: &Field) -> bool {
+    field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .any(|attr| match &attr.meta {
+            Meta::NameValue(doc) => matches!(
+                &doc.value,
+                Expr::Lit(expr) if matches!(
+                    &expr.lit,
+                    Lit::Str(line) if line.value().trim_start().starts_with("CHECK:")
+                )
+            ),
+            _ => false,
+        })
+}
+
+// Last path segment, e.g. `Signer` for `mini_anchor::Signer<'info>`
+pub(crate) fn type_ident(ty: &Type) -> Option<&Ident> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    type_path.path.segments.last().map(|segment| &segment.ident)
+}
+
+// Replaces the struct's lifetime with `'static`
+struct StaticLifetime<'a>(&'a Lifetime);
+
+impl VisitMut for StaticLifetime<'_> {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime == self.0 {
+            *lifetime = Lifetime::new("'static", lifetime.span());
+        }
+    }
+}
+
+// `ty` with the struct's lifetime replaced by `'static`, so the bumps struct,
+// which has no lifetime, can name `<Nested<'static> as Bumps>::Bumps`
+pub(crate) fn with_static_lifetime(ty: &Type, lifetime: &Lifetime) -> Type {
+    let mut ty = ty.clone();
+    StaticLifetime(lifetime).visit_type_mut(&mut ty);
+    ty
+}
+
+// `ty` with every lifetime of the struct replaced by `'static`, for the const
+// assertions in the generated code to name it
+pub(crate) fn with_static_lifetimes(ty: &Type, generics: &Generics) -> Type {
+    let mut ty = ty.clone();
+    for lifetime in generics.lifetimes() {
+        StaticLifetime(&lifetime.lifetime).visit_type_mut(&mut ty);
+    }
+    ty
+}
+
+// Whether `ty` refers to one of the struct's type or const parameters
+pub(crate) fn mentions_type_params(ty: &Type, generics: &Generics) -> bool {
+    let params: Vec<_> = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .chain(generics.const_params().map(|param| &param.ident))
+        .collect();
+    mentions_ident(ty.to_token_stream(), &params)
+}
+
+fn mentions_ident(tokens: TokenStream, idents: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&&ident),
+        TokenTree::Group(group) => mentions_ident(group.stream(), idents),
+        _ => false,
+    })
+}
+
+// The struct's type and const parameters with their bounds, for the bumps struct.
+// It has no lifetime, so bounds mentioning the struct's lifetimes are dropped. The
+// bumps of each field add the bounds they need.
+pub(crate) fn bumps_generics(generics: &Generics) -> Generics {
+    let lifetimes: Vec<_> = generics.lifetimes().map(|param| &param.lifetime).collect();
+    let mut bumps_generics = generics.clone();
+    bumps_generics.params = generics
+        .params
+        .iter()
+        .cloned()
+        .filter_map(|param| match param {
+            GenericParam::Lifetime(_) => None,
+            GenericParam::Type(mut param) => {
+                param.default = None;
+                param.bounds = without_lifetime_bounds(param.bounds, &lifetimes);
+                Some(GenericParam::Type(param))
+            }
+            GenericParam::Const(mut param) => {
+                param.default = None;
+                Some(GenericParam::Const(param))
+            }
+        })
+        .collect();
+
+    if let Some(where_clause) = &mut bumps_generics.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .clone()
+            .into_iter()
+            .filter_map(|predicate| match predicate {
+                WherePredicate::Type(mut predicate)
+                    if !mentions_lifetime(&predicate.bounded_ty, &lifetimes) =>
+                {
+                    predicate.bounds = without_lifetime_bounds(predicate.bounds, &lifetimes);
+                    (!predicate.bounds.is_empty()).then_some(WherePredicate::Type(predicate))
+                }
+                _ => None,
+            })
+            .collect();
+    }
+
+    bumps_generics
+}
+
+// Drops `T: 'info` as well as bounds like `T: Accounts<'info>`
+fn without_lifetime_bounds(
+    bounds: Punctuated<TypeParamBound, Token![+]>,
+    lifetimes: &[&Lifetime],
+) -> Punctuated<TypeParamBound, Token![+]> {
+    bounds
+        .into_iter()
+        .filter(|bound| {
+            !matches!(bound, TypeParamBound::Lifetime(_)) && !mentions_lifetime(bound, lifetimes)
+        })
+        .collect()
+}
+
+fn mentions_lifetime(node: &impl ToTokens, lifetimes: &[&Lifetime]) -> bool {
+    let idents: Vec<_> = lifetimes.iter().map(|lifetime| &lifetime.ident).collect();
+    mentions_lifetime_ident(node.to_token_stream(), &idents)
+}
+
+// A lifetime is a `'` followed by its ident
+fn mentions_lifetime_ident(tokens: TokenStream, idents: &[&Ident]) -> bool {
+    let mut after_quote = false;
+    tokens.into_iter().any(|token| {
+        let found = match &token {
+            TokenTree::Ident(ident) => after_quote && idents.contains(&ident),
+            TokenTree::Group(group) => mentions_lifetime_ident(group.stream(), idents),
+            _ => false,
+        };
+        after_quote = matches!(&token, TokenTree::Punct(punct) if punct.as_char() == '\'');
+        found
+    })
+}
+
+// `Account<'info, T>` from `Account<'info, T>`, `Box<Account<'info, T>>`,
+// `Option<Account<'info, T>>` or `Option<Box<Account<'info, T>>>`
+pub(crate) fn unwrap_account_type(ty: &Type) -> &Type {
+    let ty = wrapped_type(ty, "Option").unwrap_or(ty);
+    wrapped_type(ty, "Box").unwrap_or(ty)
+}
+
+// `Option<...>` fields are `None` when the program id is passed in their slot
+pub(crate) fn is_optional(ty: &Type) -> bool {
+    wrapped_type(ty, "Option").is_some()
+}
+
+pub(crate) fn is_boxed(ty: &Type) -> bool {
+    let ty = wrapped_type(ty, "Option").unwrap_or(ty);
+    wrapped_type(ty, "Box").is_some()
+}
+
+// `T` from `Wrapper<T>`
+fn wrapped_type<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    if type_ident(ty)? != wrapper {
+        return None;
+    }
+    last_type_argument(ty)
+}
+
+fn last_type_argument(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    args.args.iter().rev().find_map(|arg| match arg {
+        GenericArgument::Type(inner_type) => Some(inner_type),
+        _ => None,
+    })
+}
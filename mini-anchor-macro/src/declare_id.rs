@@ -38,6 +38,15 @@ pub fn declare_id_impl(input: TokenStream) -> TokenStream {
         pub fn check_id(pubkey: &::mini_anchor::solana_program::pubkey::Pubkey) -> bool {
             pubkey == &ID
         }
+
+        // Marker type for this program, used as `Program<'info, ThisProgram>`
+        pub struct ThisProgram;
+
+        impl ::mini_anchor::Id for ThisProgram {
+            fn id() -> ::mini_anchor::solana_program::pubkey::Pubkey {
+                ID
+            }
+        }
     };
 
     expanded.into()
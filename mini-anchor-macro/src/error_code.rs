@@ -0,0 +1,108 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Error as SynError, Fields, LitInt, LitStr};
+
+// Anchor-compatible default, leaves room for the framework's own codes
+const DEFAULT_OFFSET: u32 = 6000;
+
+pub fn error_code_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut offset = DEFAULT_OFFSET;
+    let attr_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("offset") {
+            let lit: LitInt = meta.value()?.parse()?;
+            offset = lit.base10_parse()?;
+            Ok(())
+        } else {
+            Err(meta.error("unsupported error_code argument, expected `offset = N`"))
+        }
+    });
+    parse_macro_input!(attr with attr_parser);
+
+    let mut input = parse_macro_input!(item as syn::ItemEnum);
+
+    let enum_name = &input.ident;
+
+    let mut variant_names = Vec::new();
+    let mut messages = Vec::new();
+
+    for variant in input.variants.iter_mut() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return SynError::new(variant.span(), "error_code variants cannot have fields")
+                .to_compile_error()
+                .into();
+        }
+
+        // #[msg("...")] is optional, the variant name is used otherwise
+        let mut message = None;
+        for attr in variant.attrs.iter().filter(|a| a.path().is_ident("msg")) {
+            match attr.parse_args::<LitStr>() {
+                Ok(lit) => message = Some(lit.value()),
+                Err(err) => return err.to_compile_error().into(),
+            }
+        }
+        variant.attrs.retain(|a| !a.path().is_ident("msg"));
+
+        messages.push(message.unwrap_or_else(|| variant.ident.to_string()));
+        variant_names.push(variant.ident.clone());
+    }
+
+    let variant_strs = variant_names.iter().map(|name| name.to_string());
+
+    quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[repr(u32)]
+        #input
+
+        impl #enum_name {
+            // Added to each variant's discriminant to form its error code
+            pub const OFFSET: u32 = #offset;
+
+            // Value carried by ProgramError::Custom
+            pub fn code(&self) -> u32 {
+                Self::OFFSET + *self as u32
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    #(Self::#variant_names => #variant_strs),*
+                }
+            }
+
+            pub fn message(&self) -> &'static str {
+                match self {
+                    #(Self::#variant_names => #messages),*
+                }
+            }
+
+            // Map a custom error code from a failed transaction back to its variant
+            pub fn from_code(code: u32) -> Option<Self> {
+                let discriminant = code.checked_sub(Self::OFFSET)?;
+                #(
+                    if discriminant == Self::#variant_names as u32 {
+                        return Some(Self::#variant_names);
+                    }
+                )*
+                None
+            }
+        }
+
+        impl ::core::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(self.message())
+            }
+        }
+
+        impl From<#enum_name> for ::mini_anchor::solana_program::program_error::ProgramError {
+            fn from(error: #enum_name) -> Self {
+                ::mini_anchor::solana_program::msg!(
+                    "Error Code: {}. Error Number: {}. Error Message: {}.",
+                    error.name(),
+                    error.code(),
+                    error.message()
+                );
+                ::mini_anchor::solana_program::program_error::ProgramError::Custom(error.code())
+            }
+        }
+    }
+    .into()
+}
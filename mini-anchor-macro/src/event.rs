@@ -0,0 +1,50 @@
+use crate::account::{
+    generate_discriminator, generate_serialization, generate_struct, parse_fields,
+};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Error as SynError, ItemStruct};
+
+pub fn event_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return SynError::new_spanned(
+            proc_macro2::TokenStream::from(attr),
+            "#[event] takes no arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let input = parse_macro_input!(item as ItemStruct);
+
+    let struct_name = &input.ident;
+    let discriminator = generate_discriminator("event", &struct_name.to_string());
+
+    // Same layout as an account: fields are sized and serialized the same way
+    let fields = match parse_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let space_calculation = &fields.space;
+
+    let definition = generate_struct(&input, &fields);
+    let serialization = generate_serialization(struct_name, &fields);
+
+    quote! {
+        #definition
+
+        impl ::mini_anchor::Discriminator for #struct_name {
+            const DISCRIMINATOR: [u8; 8] = [#(#discriminator),*];
+        }
+
+        // Largest possible payload, `emit!` serializes into a buffer of this size
+        impl ::mini_anchor::Space for #struct_name {
+            const SPACE: usize = 8 #(+ #space_calculation)*;
+        }
+
+        impl ::mini_anchor::Event for #struct_name {}
+
+        #serialization
+    }
+    .into()
+}
@@ -1,6 +1,9 @@
 mod account;
 mod accounts;
 mod declare_id;
+mod error_code;
+mod event;
+mod program;
 
 use proc_macro::TokenStream;
 
@@ -10,11 +13,26 @@ pub fn declare_id(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro_attribute]
-pub fn account(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    account::account_impl(item)
+pub fn account(attr: TokenStream, item: TokenStream) -> TokenStream {
+    account::account_impl(attr, item)
 }
 
-#[proc_macro_derive(Accounts, attributes(account))]
+#[proc_macro_attribute]
+pub fn event(attr: TokenStream, item: TokenStream) -> TokenStream {
+    event::event_impl(attr, item)
+}
+
+#[proc_macro_derive(Accounts, attributes(account, instruction))]
 pub fn derive_accounts(input: TokenStream) -> TokenStream {
     accounts::derive_accounts_impl(input)
 }
+
+#[proc_macro_attribute]
+pub fn program(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    program::program_impl(item)
+}
+
+#[proc_macro_attribute]
+pub fn error_code(attr: TokenStream, item: TokenStream) -> TokenStream {
+    error_code::error_code_impl(attr, item)
+}
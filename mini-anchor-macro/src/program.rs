@@ -0,0 +1,271 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Error as SynError, FnArg, GenericArgument, Ident, Item,
+    ItemFn, ItemMod, Pat, PathArguments, Type, Visibility,
+};
+
+use crate::account::generate_discriminator;
+
+struct Handler<'a> {
+    name: &'a Ident,
+    ix_name: Ident,
+    discriminator: [u8; 8],
+    accounts_type: Type,
+    arg_names: Vec<&'a Ident>,
+    arg_types: Vec<&'a Type>,
+}
+
+pub fn program_impl(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as ItemMod);
+
+    let mod_name = &input.ident;
+
+    let items = match &input.content {
+        Some((_, items)) => items,
+        None => {
+            return SynError::new(input.span(), "#[program] requires an inline module")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    // Every `pub fn` in the module is an instruction handler
+    let mut handlers = Vec::new();
+    for item in items {
+        let Item::Fn(item_fn) = item else {
+            continue;
+        };
+        if !matches!(item_fn.vis, Visibility::Public(_)) {
+            continue;
+        }
+        match parse_handler(item_fn) {
+            Ok(handler) => handlers.push(handler),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+
+    let ix_structs = handlers.iter().map(|handler| {
+        let ix_name = &handler.ix_name;
+        let discriminator = &handler.discriminator;
+        let arg_names = &handler.arg_names;
+        let arg_types = &handler.arg_types;
+
+        quote! {
+            pub struct #ix_name {
+                #(pub #arg_names: #arg_types),*
+            }
+
+            impl #ix_name {
+                // 8-byte discriminator
+                pub const DISCRIMINATOR: [u8; 8] = [#(#discriminator),*];
+            }
+
+            impl ::mini_anchor::AnchorSerialize for #ix_name {
+                // Serialize discriminator followed by the handler arguments
+                fn serialize(&self, buf: &mut [u8]) -> Result<usize, ::mini_anchor::solana_program::program_error::ProgramError> {
+                    if buf.len() < 8 {
+                        return Err(::mini_anchor::error::ErrorCode::InstructionDidNotSerialize.into());
+                    }
+
+                    buf[..8].copy_from_slice(&Self::DISCRIMINATOR);
+                    #[allow(unused_mut)]
+                    let mut offset = 8;
+
+                    #(
+                        {
+                            let written = <#arg_types as ::mini_anchor::AnchorSerialize>::serialize(
+                                &self.#arg_names,
+                                &mut buf[offset..]
+                            );
+                            offset += written?;
+                        }
+                    )*
+
+                    Ok(offset)
+                }
+            }
+
+            impl ::mini_anchor::AnchorDeserialize for #ix_name {
+                // Deserialize from raw instruction data, discriminator included
+                fn deserialize(data: &[u8]) -> Result<(Self, usize), ::mini_anchor::solana_program::program_error::ProgramError> {
+                    if data.len() < 8 || data[..8] != Self::DISCRIMINATOR {
+                        return Err(::mini_anchor::error::ErrorCode::InstructionDidNotDeserialize.into());
+                    }
+
+                    #[allow(unused_mut)]
+                    let mut offset = 8;
+
+                    Ok((Self {
+                        #(
+                            #arg_names: {
+                                let (value, read) = <#arg_types as ::mini_anchor::AnchorDeserialize>::deserialize(&data[offset..])?;
+                                offset += read;
+                                value
+                            }
+                        ),*
+                    }, offset))
+                }
+            }
+        }
+    });
+
+    let dispatch_arms = handlers.iter().map(|handler| {
+        let name = handler.name;
+        let ix_name = &handler.ix_name;
+        let accounts_type = &handler.accounts_type;
+        let arg_names = &handler.arg_names;
+
+        quote! {
+            instruction::#ix_name::DISCRIMINATOR => {
+                let (ix, _) = <instruction::#ix_name as ::mini_anchor::AnchorDeserialize>::deserialize(data)
+                    .map_err(|_| ::mini_anchor::error::ErrorCode::InstructionDidNotDeserialize)?;
+
+                let mut bumps = <<#accounts_type as ::mini_anchor::Bumps>::Bumps as Default>::default();
+                let mut remaining_accounts: &[::mini_anchor::solana_program::account_info::AccountInfo<'info>] = accounts;
+                let mut validated = <#accounts_type as ::mini_anchor::Accounts>::try_accounts(program_id, &mut remaining_accounts, &data[8..], &mut bumps)?;
+
+                let ctx = ::mini_anchor::Context::new(program_id, &mut validated, remaining_accounts, bumps);
+                #mod_name::#name(ctx, #(ix.#arg_names),*)?;
+
+                // Persist mutated accounts now that the handler has succeeded
+                ::mini_anchor::AccountsExit::exit(&validated, program_id)
+            }
+        }
+    });
+
+    quote! {
+        #input
+
+        /// Instruction data for each handler, used to build transactions.
+        pub mod instruction {
+            use super::*;
+
+            #(#ix_structs)*
+        }
+
+        #[cfg(not(feature = "no-entrypoint"))]
+        ::mini_anchor::solana_program::entrypoint!(entry);
+
+        /// Routes instruction data to the matching handler by its discriminator.
+        pub fn entry<'info>(
+            program_id: &::mini_anchor::solana_program::pubkey::Pubkey,
+            accounts: &'info [::mini_anchor::solana_program::account_info::AccountInfo<'info>],
+            data: &[u8],
+        ) -> ::mini_anchor::solana_program::entrypoint::ProgramResult {
+            if data.len() < 8 {
+                return Err(::mini_anchor::error::ErrorCode::InstructionMissing.into());
+            }
+
+            let mut sighash = [0u8; 8];
+            sighash.copy_from_slice(&data[..8]);
+
+            match sighash {
+                #(#dispatch_arms)*
+                _ => Err(::mini_anchor::error::ErrorCode::InstructionFallbackNotFound.into()),
+            }
+        }
+    }
+    .into()
+}
+
+fn parse_handler(item_fn: &ItemFn) -> Result<Handler<'_>, SynError> {
+    let name = &item_fn.sig.ident;
+    let mut inputs = item_fn.sig.inputs.iter();
+
+    // First argument is `Context<T>`, where `T` is the accounts struct
+    let accounts_type = match inputs.next() {
+        Some(FnArg::Typed(pat_type)) => match context_accounts_type(&pat_type.ty) {
+            Some(ty) => elide_lifetimes(ty),
+            None => {
+                return Err(SynError::new(
+                    pat_type.ty.span(),
+                    "Expected `Context<T>` where `T` derives `Accounts`",
+                ));
+            }
+        },
+        _ => {
+            return Err(SynError::new(
+                item_fn.sig.span(),
+                "Instruction handlers must take `Context<T>` as their first argument",
+            ));
+        }
+    };
+
+    let mut arg_names = Vec::new();
+    let mut arg_types = Vec::new();
+
+    for input in inputs {
+        let FnArg::Typed(pat_type) = input else {
+            return Err(SynError::new(input.span(), "Unexpected receiver"));
+        };
+        let Pat::Ident(pat_ident) = pat_type.pat.as_ref() else {
+            return Err(SynError::new(
+                pat_type.pat.span(),
+                "Instruction arguments must be plain identifiers",
+            ));
+        };
+        arg_names.push(&pat_ident.ident);
+        arg_types.push(pat_type.ty.as_ref());
+    }
+
+    Ok(Handler {
+        name,
+        ix_name: format_ident!("{}", to_pascal_case(&name.to_string())),
+        discriminator: generate_discriminator("global", &name.to_string()),
+        accounts_type,
+        arg_names,
+        arg_types,
+    })
+}
+
+// Extract `T` from `Context<T>` / `Context<'_, '_, T>`
+fn context_accounts_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Context" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    args.args.iter().rev().find_map(|arg| match arg {
+        GenericArgument::Type(inner_type) => Some(inner_type),
+        _ => None,
+    })
+}
+
+// Replace named lifetimes with `'_` so the type can be used inside `entry`
+fn elide_lifetimes(ty: &Type) -> Type {
+    let mut ty = ty.clone();
+    if let Type::Path(type_path) = &mut ty {
+        for segment in type_path.path.segments.iter_mut() {
+            if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                for arg in args.args.iter_mut() {
+                    if let GenericArgument::Lifetime(lifetime) = arg {
+                        *lifetime = syn::Lifetime::new("'_", lifetime.span());
+                    }
+                }
+            }
+        }
+    }
+    ty
+}
+
+fn to_pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
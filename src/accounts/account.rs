@@ -1,31 +1,133 @@
-use crate::traits::AnchorDeserialize;
+use super::next_account;
+use crate::error::{log_mismatch, ErrorCode};
+use crate::traits::{
+    Accounts, AccountsClose, AccountsExit, AnchorDeserialize, AnchorSerialize, Bumps,
+    Discriminator, Owner, ToAccountInfos,
+};
 use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
-use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 
 pub struct Account<'info, T> {
     pub info: &'info AccountInfo<'info>,
-    _marker: PhantomData<T>,
+    account: T,
+    // Set by `DerefMut`, so read-only accounts can reject changes on exit
+    dirty: bool,
 }
 
-impl<'info, T> Account<'info, T> {
-    pub fn new(info: &'info AccountInfo<'info>) -> Self {
-        Self {
+impl<'info, T: AnchorDeserialize + Owner> Account<'info, T> {
+    // Verify the owner and deserialize the account data once, up front
+    pub fn try_from(info: &'info AccountInfo<'info>) -> Result<Self, ProgramError> {
+        if info.owner != &T::owner() {
+            log_mismatch(T::owner(), info.owner);
+            return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+
+        let data = info.try_borrow_data()?;
+        let (account, _) = T::deserialize(&data)?;
+
+        Ok(Self {
             info,
-            _marker: PhantomData,
+            account,
+            dirty: false,
+        })
+    }
+}
+
+impl<'info, T: AnchorDeserialize + Discriminator + Owner> Account<'info, T> {
+    // Stamp the discriminator onto a freshly created, zeroed account and load it
+    pub fn try_init(info: &'info AccountInfo<'info>) -> Result<Self, ProgramError> {
+        if info.owner != &T::owner() {
+            log_mismatch(T::owner(), info.owner);
+            return Err(ErrorCode::AccountOwnedByWrongProgram.into());
+        }
+
+        {
+            let mut data = info.try_borrow_mut_data()?;
+            if data.len() < 8 {
+                return Err(ProgramError::AccountDataTooSmall);
+            }
+            data[..8].copy_from_slice(&T::DISCRIMINATOR);
         }
+
+        Self::try_from(info)
     }
+}
+
+impl<'info, T: AnchorDeserialize + Owner> Accounts<'info> for Account<'info, T> {
+    const HOLDS_DATA: bool = true;
+
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut (),
+    ) -> Result<Self, ProgramError> {
+        Self::try_from(next_account(accounts)?)
+    }
+}
+
+impl<T> Bumps for Account<'_, T> {
+    type Bumps = ();
+}
+
+impl<'info, T: AnchorSerialize + Owner> AccountsExit<'info> for Account<'info, T> {
+    // Write the in-memory value back to the account data
+    fn exit(&self, program_id: &Pubkey) -> Result<(), ProgramError> {
+        // Only accounts still owned by this program can be written
+        if self.info.owner != program_id {
+            return Ok(());
+        }
+
+        let mut data = self.info.try_borrow_mut_data()?;
+        self.account.serialize(&mut data)?;
+        Ok(())
+    }
+
+    // Changes to a read-only account would be silently dropped, so reject them
+    fn exit_read_only(&self) -> Result<(), ProgramError> {
+        if self.dirty {
+            return Err(ErrorCode::AccountNotMutable.into());
+        }
+        Ok(())
+    }
+}
+
+impl<'info, T> AccountsClose<'info> for Account<'info, T> {
+    fn close(&self, sol_destination: &AccountInfo<'info>) -> Result<(), ProgramError> {
+        crate::common::close(self.info, sol_destination)
+    }
+}
+
+impl<T> Account<'_, T> {
+    pub fn into_inner(self) -> T {
+        self.account
+    }
+}
+
+impl<'info, T> AsRef<AccountInfo<'info>> for Account<'info, T> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        self.info
+    }
+}
+
+impl<'info, T> ToAccountInfos<'info> for Account<'info, T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<T> Deref for Account<'_, T> {
+    type Target = T;
 
-    pub fn key(&self) -> &Pubkey {
-        self.info.key
+    fn deref(&self) -> &Self::Target {
+        &self.account
     }
 }
 
-// Deserialize account data into T
-impl<T: AnchorDeserialize> Account<'_, T> {
-    pub fn data(&self) -> Result<T, ProgramError> {
-        let data = self.info.data.borrow();
-        let (value, _) = T::deserialize(&data)?;
-        Ok(value)
+impl<T> DerefMut for Account<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.dirty = true;
+        &mut self.account
     }
 }
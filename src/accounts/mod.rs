@@ -1,7 +1,48 @@
 mod account;
 mod program;
 mod signer;
+mod system_account;
+mod sysvar;
+mod unchecked_account;
 
 pub use account::Account;
 pub use program::Program;
 pub use signer::Signer;
+pub use system_account::SystemAccount;
+pub use sysvar::Sysvar;
+pub use unchecked_account::UncheckedAccount;
+
+use crate::error::ErrorCode;
+use crate::traits::{Accounts, AccountsExit, Bumps};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+// Takes the next account off the front of `accounts`
+fn next_account<'info>(
+    accounts: &mut &'info [AccountInfo<'info>],
+) -> Result<&'info AccountInfo<'info>, ProgramError> {
+    let (info, rest) = accounts
+        .split_first()
+        .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+    *accounts = rest;
+    Ok(info)
+}
+
+// A raw account, loaded without any checks
+impl<'info> Accounts<'info> for AccountInfo<'info> {
+    const UNCHECKED: bool = true;
+
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut (),
+    ) -> Result<Self, ProgramError> {
+        Ok(next_account(accounts)?.clone())
+    }
+}
+
+impl Bumps for AccountInfo<'_> {
+    type Bumps = ();
+}
+
+impl<'info> AccountsExit<'info> for AccountInfo<'info> {}
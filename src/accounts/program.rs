@@ -1,4 +1,7 @@
-use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+use super::next_account;
+use crate::error::{log_mismatch, ErrorCode};
+use crate::traits::{Accounts, AccountsExit, Bumps, Id, ToAccountInfos};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
 use std::marker::PhantomData;
 
@@ -7,15 +10,50 @@ pub struct Program<'info, T> {
     _marker: PhantomData<T>,
 }
 
-impl<'info, T> Program<'info, T> {
-    pub fn new(info: &'info AccountInfo<'info>) -> Self {
-        Self {
+impl<'info, T: Id> Program<'info, T> {
+    // Only the program `T` stands for is accepted
+    pub fn try_from(info: &'info AccountInfo<'info>) -> Result<Self, ProgramError> {
+        if info.key != &T::id() {
+            log_mismatch(T::id(), info.key);
+            return Err(ErrorCode::InvalidProgramId.into());
+        }
+        if !info.executable {
+            return Err(ErrorCode::InvalidProgramExecutable.into());
+        }
+
+        Ok(Self {
             info,
             _marker: PhantomData,
-        }
+        })
+    }
+}
+
+impl<'info, T: Id> Accounts<'info> for Program<'info, T> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut (),
+    ) -> Result<Self, ProgramError> {
+        Self::try_from(next_account(accounts)?)
     }
+}
+
+impl<T> Bumps for Program<'_, T> {
+    type Bumps = ();
+}
+
+// Nothing to write back
+impl<'info, T> AccountsExit<'info> for Program<'info, T> {}
+
+impl<'info, T> AsRef<AccountInfo<'info>> for Program<'info, T> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        self.info
+    }
+}
 
-    pub fn key(&self) -> &Pubkey {
-        self.info.key
+impl<'info, T> ToAccountInfos<'info> for Program<'info, T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
     }
 }
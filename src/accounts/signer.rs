@@ -1,18 +1,49 @@
-use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+use super::next_account;
+use crate::error::ErrorCode;
+use crate::traits::{Accounts, AccountsExit, Bumps, IsSigner, ToAccountInfos};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
 pub struct Signer<'info> {
     pub info: &'info AccountInfo<'info>,
 }
 
 impl<'info> Signer<'info> {
-    pub fn new(info: &'info AccountInfo<'info>) -> Result<Self, &'static str> {
+    pub fn new(info: &'info AccountInfo<'info>) -> Result<Self, ProgramError> {
         if !info.is_signer {
-            return Err("Account must be a signer");
+            return Err(ErrorCode::AccountNotSigner.into());
         }
         Ok(Self { info })
     }
+}
+
+impl<'info> Accounts<'info> for Signer<'info> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut (),
+    ) -> Result<Self, ProgramError> {
+        Self::new(next_account(accounts)?)
+    }
+}
+
+impl Bumps for Signer<'_> {
+    type Bumps = ();
+}
+
+// Nothing to write back
+impl<'info> AccountsExit<'info> for Signer<'info> {}
+
+impl IsSigner for Signer<'_> {}
+
+impl<'info> AsRef<AccountInfo<'info>> for Signer<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        self.info
+    }
+}
 
-    pub fn key(&self) -> &Pubkey {
-        self.info.key
+impl<'info> ToAccountInfos<'info> for Signer<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
     }
 }
@@ -0,0 +1,50 @@
+use super::next_account;
+use crate::error::{log_mismatch, ErrorCode};
+use crate::system_program;
+use crate::traits::{Accounts, AccountsExit, Bumps, ToAccountInfos};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+// A wallet or not-yet-created address, owned by the System Program
+pub struct SystemAccount<'info> {
+    pub info: &'info AccountInfo<'info>,
+}
+
+impl<'info> SystemAccount<'info> {
+    pub fn try_from(info: &'info AccountInfo<'info>) -> Result<Self, ProgramError> {
+        if !system_program::check_id(info.owner) {
+            log_mismatch(system_program::ID, info.owner);
+            return Err(ErrorCode::AccountNotSystemOwned.into());
+        }
+        Ok(Self { info })
+    }
+}
+
+impl<'info> Accounts<'info> for SystemAccount<'info> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut (),
+    ) -> Result<Self, ProgramError> {
+        Self::try_from(next_account(accounts)?)
+    }
+}
+
+impl Bumps for SystemAccount<'_> {
+    type Bumps = ();
+}
+
+// Nothing to write back
+impl<'info> AccountsExit<'info> for SystemAccount<'info> {}
+
+impl<'info> AsRef<AccountInfo<'info>> for SystemAccount<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        self.info
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for SystemAccount<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
@@ -0,0 +1,65 @@
+use super::next_account;
+use crate::error::{log_mismatch, ErrorCode};
+use crate::traits::{Accounts, AccountsExit, Bumps, ToAccountInfos};
+use solana_program::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey,
+    sysvar::Sysvar as SolanaSysvar,
+};
+
+use std::ops::Deref;
+
+// A sysvar account such as `Sysvar<'info, Clock>`, deserialized up front
+pub struct Sysvar<'info, T> {
+    pub info: &'info AccountInfo<'info>,
+    sysvar: T,
+}
+
+impl<'info, T: SolanaSysvar> Sysvar<'info, T> {
+    pub fn try_from(info: &'info AccountInfo<'info>) -> Result<Self, ProgramError> {
+        if !T::check_id(info.key) {
+            log_mismatch(T::id(), info.key);
+            return Err(ErrorCode::AccountSysvarMismatch.into());
+        }
+
+        let sysvar = T::from_account_info(info)?;
+        Ok(Self { info, sysvar })
+    }
+}
+
+impl<'info, T: SolanaSysvar> Accounts<'info> for Sysvar<'info, T> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut (),
+    ) -> Result<Self, ProgramError> {
+        Self::try_from(next_account(accounts)?)
+    }
+}
+
+impl<T> Bumps for Sysvar<'_, T> {
+    type Bumps = ();
+}
+
+// Nothing to write back
+impl<'info, T> AccountsExit<'info> for Sysvar<'info, T> {}
+
+impl<'info, T> AsRef<AccountInfo<'info>> for Sysvar<'info, T> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        self.info
+    }
+}
+
+impl<'info, T> ToAccountInfos<'info> for Sysvar<'info, T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<T> Deref for Sysvar<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sysvar
+    }
+}
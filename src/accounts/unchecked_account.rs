@@ -0,0 +1,57 @@
+use super::next_account;
+use crate::traits::{Accounts, AccountsExit, Bumps, ToAccountInfos};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use std::ops::Deref;
+
+// Any account, with no checks at all. Fields of this type must carry a
+// `/// CHECK:` doc comment saying why that is safe.
+pub struct UncheckedAccount<'info> {
+    pub info: &'info AccountInfo<'info>,
+}
+
+impl<'info> UncheckedAccount<'info> {
+    pub fn new(info: &'info AccountInfo<'info>) -> Self {
+        Self { info }
+    }
+}
+
+impl<'info> Accounts<'info> for UncheckedAccount<'info> {
+    const UNCHECKED: bool = true;
+
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut (),
+    ) -> Result<Self, ProgramError> {
+        Ok(Self::new(next_account(accounts)?))
+    }
+}
+
+impl Bumps for UncheckedAccount<'_> {
+    type Bumps = ();
+}
+
+// Nothing to write back
+impl<'info> AccountsExit<'info> for UncheckedAccount<'info> {}
+
+impl<'info> AsRef<AccountInfo<'info>> for UncheckedAccount<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        self.info
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for UncheckedAccount<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+impl<'info> Deref for UncheckedAccount<'info> {
+    type Target = AccountInfo<'info>;
+
+    fn deref(&self) -> &Self::Target {
+        self.info
+    }
+}
@@ -0,0 +1,34 @@
+use crate::system_program;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+// Drain the account into `sol_destination` and hand it back to the System Program.
+// The runtime only allows changing the owner of zeroed data, so the closed state
+// itself is the marker: no lamports, no data and owned by the System Program.
+// `Account::try_from` rejects it, and any lamports sent to it later cannot bring
+// the old data back.
+pub fn close<'info>(
+    info: &AccountInfo<'info>,
+    sol_destination: &AccountInfo<'info>,
+) -> ProgramResult {
+    let lamports = info.lamports();
+    **sol_destination.try_borrow_mut_lamports()? = sol_destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+
+    info.try_borrow_mut_data()?.fill(0);
+    info.assign(&system_program::ID);
+    Ok(())
+}
+
+// True for accounts left behind by `close` (or never created)
+pub fn is_closed(info: &AccountInfo) -> bool {
+    info.lamports() == 0
+        && system_program::check_id(info.owner)
+        && info
+            .try_borrow_data()
+            .is_ok_and(|data| data.iter().all(|byte| *byte == 0))
+}
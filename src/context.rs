@@ -0,0 +1,33 @@
+use crate::traits::Bumps;
+use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+/// Everything an instruction handler receives besides its arguments.
+///
+/// `'a` is the lifetime of the borrows made by the generated entrypoint and
+/// `'info` is the lifetime of the underlying `AccountInfo`s.
+pub struct Context<'a, 'info, T: Bumps> {
+    /// Id of the currently executing program.
+    pub program_id: &'a Pubkey,
+    /// Accounts validated by `#[derive(Accounts)]`.
+    pub accounts: &'a mut T,
+    /// Accounts passed after the last field declared in `T`.
+    pub remaining_accounts: &'a [AccountInfo<'info>],
+    /// Bump seeds found while validating the accounts.
+    pub bumps: T::Bumps,
+}
+
+impl<'a, 'info, T: Bumps> Context<'a, 'info, T> {
+    pub fn new(
+        program_id: &'a Pubkey,
+        accounts: &'a mut T,
+        remaining_accounts: &'a [AccountInfo<'info>],
+        bumps: T::Bumps,
+    ) -> Self {
+        Self {
+            program_id,
+            accounts,
+            remaining_accounts,
+            bumps,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error_code;
+use solana_program::{msg, program_error::ProgramError};
+
+/// Errors raised by the framework itself. Codes below 6000 are reserved for
+/// these, user-defined `#[error_code]` enums start at 6000 by default.
+#[error_code(offset = 0)]
+pub enum ErrorCode {
+    // Instructions
+    #[msg("8 byte instruction identifier not provided")]
+    InstructionMissing = 100,
+    #[msg("No instruction matches the given discriminator")]
+    InstructionFallbackNotFound = 101,
+    #[msg("The program could not deserialize the given instruction")]
+    InstructionDidNotDeserialize = 102,
+    #[msg("The program could not serialize the given instruction")]
+    InstructionDidNotSerialize = 103,
+
+    // Constraints
+    #[msg("A mut constraint was violated")]
+    ConstraintMut = 2000,
+    #[msg("A has one constraint was violated")]
+    ConstraintHasOne = 2001,
+    #[msg("A signer constraint was violated")]
+    ConstraintSigner = 2002,
+    #[msg("A raw constraint was violated")]
+    ConstraintRaw = 2003,
+    #[msg("An owner constraint was violated")]
+    ConstraintOwner = 2004,
+    #[msg("A rent exemption constraint was violated")]
+    ConstraintRentExempt = 2005,
+    #[msg("A seeds constraint was violated")]
+    ConstraintSeeds = 2006,
+    #[msg("A close constraint was violated")]
+    ConstraintClose = 2011,
+    #[msg("An address constraint was violated")]
+    ConstraintAddress = 2012,
+    #[msg("Expected zero account discriminant")]
+    ConstraintZero = 2013,
+    #[msg("A space constraint was violated")]
+    ConstraintSpace = 2019,
+    #[msg("A mutable account was passed more than once")]
+    ConstraintDuplicateMutableAccount = 2040,
+
+    // Accounts
+    #[msg("No discriminator was found on the account")]
+    AccountDiscriminatorNotFound = 3001,
+    #[msg("Account discriminator did not match what was expected")]
+    AccountDiscriminatorMismatch = 3002,
+    #[msg("Not enough account keys given to the instruction")]
+    AccountNotEnoughKeys = 3005,
+    #[msg("A read-only account was modified")]
+    AccountNotMutable = 3006,
+    #[msg("The given account is owned by a different program than expected")]
+    AccountOwnedByWrongProgram = 3007,
+    #[msg("Program ID was not as expected")]
+    InvalidProgramId = 3008,
+    #[msg("Program account is not executable")]
+    InvalidProgramExecutable = 3009,
+    #[msg("The given account did not sign")]
+    AccountNotSigner = 3010,
+    #[msg("The given account is not owned by the system program")]
+    AccountNotSystemOwned = 3011,
+    #[msg("The given public key does not match the required sysvar")]
+    AccountSysvarMismatch = 3015,
+    #[msg("The account reallocation exceeds the MAX_PERMITTED_DATA_INCREASE limit")]
+    AccountReallocExceedsLimit = 3016,
+}
+
+// Logs both sides of a failed comparison, e.g. the expected and the given key
+pub fn log_mismatch(expected: impl core::fmt::Display, actual: impl core::fmt::Display) {
+    msg!("Expected: {}", expected);
+    msg!("Actual: {}", actual);
+}
+
+// Names the `Struct.field` an account failed to load for, after the error itself was logged
+pub fn with_account(account: &str, error: ProgramError) -> ProgramError {
+    msg!("Caused by account: `{}`", account);
+    error
+}
@@ -0,0 +1,67 @@
+use crate::traits::Event;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_program::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+// Logs an `#[event]` struct, e.g. `emit!(Deposited { amount })?;`
+#[macro_export]
+macro_rules! emit {
+    ($event:expr) => {
+        $crate::event::emit(&$event)
+    };
+}
+
+// Logs the event as a single `Program data:` entry, its discriminator followed
+// by its fields
+pub fn emit<E: Event>(event: &E) -> Result<(), ProgramError> {
+    let mut buf = vec![0u8; E::SPACE];
+    let written = event.serialize(&mut buf)?;
+    sol_log_data(&[&buf[..written]]);
+    Ok(())
+}
+
+// Client side: recovers the `E` events emitted by `program_id` from a transaction's
+// log messages, in order. Data logged by any other program, including ones invoked
+// by `program_id`, is skipped.
+pub fn parse_events<E: Event>(
+    program_id: &Pubkey,
+    logs: &[impl AsRef<str>],
+) -> Result<Vec<E>, ProgramError> {
+    let program_id = program_id.to_string();
+    // Programs currently executing, innermost last
+    let mut invoked: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+
+        if let Some(data) = log.strip_prefix("Program data: ") {
+            // `emit!` logs exactly one base64 field per entry
+            if invoked.last() != Some(&program_id.as_str()) || data.contains(' ') {
+                continue;
+            }
+
+            let bytes = STANDARD
+                .decode(data)
+                .map_err(|_| ProgramError::InvalidArgument)?;
+            if bytes.len() < 8 || bytes[..8] != E::DISCRIMINATOR {
+                continue;
+            }
+
+            let (event, _) = E::deserialize(&bytes)?;
+            events.push(event);
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            // `Program <id> invoke [<depth>]`, `Program <id> success`
+            // or `Program <id> failed: <error>`
+            let mut words = rest.split(' ');
+            match (words.next(), words.next()) {
+                (Some(id), Some("invoke")) => invoked.push(id),
+                (Some(_), Some("success" | "failed:")) => {
+                    invoked.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(events)
+}
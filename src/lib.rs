@@ -1,19 +1,40 @@
+// Lets macro output that refers to `::mini_anchor` compile inside this crate
+extern crate self as mini_anchor;
+
 mod accounts;
+pub mod common;
+mod context;
+pub mod error;
+pub mod event;
+pub mod system_program;
 mod traits;
 mod types;
 
 pub use solana_program;
 
-pub use mini_anchor_macro::{account, declare_id, Accounts};
+pub use mini_anchor_macro::{account, declare_id, error_code, event, program, Accounts};
 
-pub use accounts::{Account, Program, Signer};
-pub use traits::{AnchorDeserialize, AnchorSerialize};
+pub use accounts::{Account, Program, Signer, SystemAccount, Sysvar, UncheckedAccount};
+pub use context::Context;
+// The `Accounts` trait shares its name with the derive, like in Anchor
+pub use traits::{
+    Accounts, AccountsClose, AccountsExit, AnchorDeserialize, AnchorSerialize, Bumps,
+    Discriminator, Event, Id, IsSigner, Key, Owner, Space, ToAccountInfo, ToAccountInfos,
+};
 
 pub mod prelude {
     pub use crate::account;
     pub use crate::declare_id;
+    pub use crate::emit;
+    pub use crate::error_code;
+    pub use crate::event;
+    pub use crate::program;
     pub use crate::solana_program;
     pub use crate::Accounts;
     pub use crate::AnchorDeserialize;
     pub use crate::AnchorSerialize;
+    pub use crate::Context;
+    pub use crate::Key;
+    pub use crate::ToAccountInfo;
+    pub use crate::ToAccountInfos;
 }
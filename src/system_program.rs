@@ -0,0 +1,79 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_system_interface::instruction as system_instruction;
+
+pub use solana_system_interface::program::{check_id, id, ID};
+
+/// Marker type for the System Program, used as `Program<'info, System>`.
+pub struct System;
+
+impl crate::Id for System {
+    fn id() -> Pubkey {
+        ID
+    }
+}
+
+// Create a rent-exempt account of `space` bytes owned by `owner`, funded by `payer`
+pub fn create_account<'info>(
+    payer: &AccountInfo<'info>,
+    new_account: &AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let required_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = new_account.lamports();
+
+    if current_lamports == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                new_account.key,
+                required_lamports,
+                space as u64,
+                owner,
+            ),
+            &[payer.clone(), new_account.clone(), system_program.clone()],
+            signer_seeds,
+        );
+    }
+
+    // `create_account` fails on funded addresses, so anyone could block
+    // creation by sending lamports first. Top up, allocate and assign instead.
+    let top_up = required_lamports.saturating_sub(current_lamports);
+    if top_up > 0 {
+        transfer(payer, new_account, top_up, system_program)?;
+    }
+
+    invoke_signed(
+        &system_instruction::allocate(new_account.key, space as u64),
+        &[new_account.clone(), system_program.clone()],
+        signer_seeds,
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(new_account.key, owner),
+        &[new_account.clone(), system_program.clone()],
+        signer_seeds,
+    )
+}
+
+// Move lamports out of a System-owned account, `from` has to sign
+pub fn transfer<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    lamports: u64,
+    system_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    invoke(
+        &system_instruction::transfer(from.key, to.key, lamports),
+        &[from.clone(), to.clone(), system_program.clone()],
+    )
+}
@@ -1,4 +1,4 @@
-use solana_program::program_error::ProgramError;
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 
 pub trait AnchorSerialize {
     fn serialize(&self, buf: &mut [u8]) -> Result<usize, ProgramError>;
@@ -7,3 +7,207 @@ pub trait AnchorSerialize {
 pub trait AnchorDeserialize: Sized {
     fn deserialize(data: &[u8]) -> Result<(Self, usize), ProgramError>;
 }
+
+// Implemented by `#[derive(Accounts)]`, links a struct to its generated bumps struct
+pub trait Bumps {
+    type Bumps: Sized + std::fmt::Debug;
+}
+
+// Loads and validates a group of accounts, implemented by `#[derive(Accounts)]`
+pub trait Accounts<'info>: Bumps + Sized {
+    // Whether the loaded value keeps its own copy of the account data, like
+    // `Account<T>`. A mutable slot holding the same account would leave that copy
+    // stale, so such accounts are checked for duplicates even when read-only.
+    const HOLDS_DATA: bool = false;
+
+    // Whether the type skips every check, like `UncheckedAccount`. Fields of such
+    // types need a `/// CHECK:` doc comment saying why that is safe.
+    const UNCHECKED: bool = false;
+
+    // Set by `#[derive(Accounts)]`: a group of accounts rather than a single one,
+    // which runs the exit of each of its own fields
+    const NESTED: bool = false;
+
+    // Consumes the declared accounts from the front of `accounts`,
+    // leaving the remaining accounts in the slice. `ix_data` is the instruction
+    // data after the discriminator, read by `#[instruction(...)]`.
+    fn try_accounts(
+        program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        ix_data: &[u8],
+        bumps: &mut Self::Bumps,
+    ) -> Result<Self, ProgramError>;
+}
+
+// The `AccountInfo` behind an account, for CPIs and code generic over account types
+pub trait ToAccountInfo<'info> {
+    fn to_account_info(&self) -> AccountInfo<'info>;
+}
+
+impl<'info, T: AsRef<AccountInfo<'info>>> ToAccountInfo<'info> for T {
+    fn to_account_info(&self) -> AccountInfo<'info> {
+        self.as_ref().clone()
+    }
+}
+
+// Every `AccountInfo` held by an account or by a whole `#[derive(Accounts)]` struct,
+// in declaration order
+pub trait ToAccountInfos<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>>;
+}
+
+impl<'info> ToAccountInfos<'info> for AccountInfo<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.clone()]
+    }
+}
+
+impl<'info, T: ToAccountInfos<'info>> ToAccountInfos<'info> for Box<T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        T::to_account_infos(self)
+    }
+}
+
+// An optional account that was not passed holds no accounts
+impl<'info, T: ToAccountInfos<'info>> ToAccountInfos<'info> for Option<T> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        self.as_ref().map_or_else(Vec::new, T::to_account_infos)
+    }
+}
+
+// Address of an account
+pub trait Key {
+    fn key(&self) -> Pubkey;
+}
+
+impl<'info, T: AsRef<AccountInfo<'info>>> Key for T {
+    fn key(&self) -> Pubkey {
+        *self.as_ref().key
+    }
+}
+
+// Account types checked to have signed the transaction, the only ones that can pay
+// for `init` and `realloc`
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot pay for accounts, payers must be a `Signer`",
+    label = "not a `Signer`"
+)]
+pub trait IsSigner {}
+
+impl<T: IsSigner> IsSigner for Box<T> {}
+
+// Boxed fields keep large accounts off the stack
+impl<'info, T: Accounts<'info>> Accounts<'info> for Box<T> {
+    const HOLDS_DATA: bool = T::HOLDS_DATA;
+    const UNCHECKED: bool = T::UNCHECKED;
+    const NESTED: bool = T::NESTED;
+
+    fn try_accounts(
+        program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        ix_data: &[u8],
+        bumps: &mut T::Bumps,
+    ) -> Result<Self, ProgramError> {
+        T::try_accounts(program_id, accounts, ix_data, bumps).map(Box::new)
+    }
+}
+
+impl<T: Bumps> Bumps for Box<T> {
+    type Bumps = T::Bumps;
+}
+
+// Optional accounts still take a slot, the program id standing in for `None`
+impl<'info, T: Accounts<'info>> Accounts<'info> for Option<T> {
+    const HOLDS_DATA: bool = T::HOLDS_DATA;
+    const UNCHECKED: bool = T::UNCHECKED;
+    const NESTED: bool = T::NESTED;
+
+    fn try_accounts(
+        program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        ix_data: &[u8],
+        bumps: &mut T::Bumps,
+    ) -> Result<Self, ProgramError> {
+        match accounts.split_first() {
+            Some((info, rest)) if info.key == program_id => {
+                *accounts = rest;
+                Ok(None)
+            }
+            _ => T::try_accounts(program_id, accounts, ix_data, bumps).map(Some),
+        }
+    }
+}
+
+impl<T: Bumps> Bumps for Option<T> {
+    type Bumps = T::Bumps;
+}
+
+// Program expected to own accounts of this type, generated by `#[account]`
+pub trait Owner {
+    fn owner() -> Pubkey;
+}
+
+// Address of a program, implemented by marker types used as `Program<'info, T>`
+pub trait Id {
+    fn id() -> Pubkey;
+}
+
+// Runs once the handler has succeeded, implemented by `#[derive(Accounts)]` and account types
+pub trait AccountsExit<'info> {
+    // Persist changes made by the handler, called for fields marked `mut`
+    fn exit(&self, _program_id: &Pubkey) -> Result<(), ProgramError> {
+        Ok(())
+    }
+
+    // Called instead of `exit` for fields not marked `mut`
+    fn exit_read_only(&self) -> Result<(), ProgramError> {
+        Ok(())
+    }
+}
+
+// Implemented by account types that `#[account(close = ...)]` can close
+pub trait AccountsClose<'info> {
+    fn close(&self, sol_destination: &AccountInfo<'info>) -> Result<(), ProgramError>;
+}
+
+// Boxed fields exit and close like the account they hold
+impl<'info, T: AccountsExit<'info>> AccountsExit<'info> for Box<T> {
+    fn exit(&self, program_id: &Pubkey) -> Result<(), ProgramError> {
+        T::exit(self, program_id)
+    }
+
+    fn exit_read_only(&self) -> Result<(), ProgramError> {
+        T::exit_read_only(self)
+    }
+}
+
+// Nothing to do for an optional account that was not passed
+impl<'info, T: AccountsExit<'info>> AccountsExit<'info> for Option<T> {
+    fn exit(&self, program_id: &Pubkey) -> Result<(), ProgramError> {
+        self.as_ref()
+            .map_or(Ok(()), |account| account.exit(program_id))
+    }
+
+    fn exit_read_only(&self) -> Result<(), ProgramError> {
+        self.as_ref().map_or(Ok(()), T::exit_read_only)
+    }
+}
+
+impl<'info, T: AccountsClose<'info>> AccountsClose<'info> for Box<T> {
+    fn close(&self, sol_destination: &AccountInfo<'info>) -> Result<(), ProgramError> {
+        T::close(self, sol_destination)
+    }
+}
+
+// 8-byte prefix identifying the type of the data that follows, generated by `#[account]`
+pub trait Discriminator {
+    const DISCRIMINATOR: [u8; 8];
+}
+
+// Maximum serialized size including the discriminator, generated by `#[account]`
+pub trait Space {
+    const SPACE: usize;
+}
+
+// Structured data logged with `emit!` for indexers, generated by `#[event]`
+pub trait Event: AnchorSerialize + AnchorDeserialize + Discriminator + Space {}
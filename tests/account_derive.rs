@@ -1,6 +1,14 @@
-use mini_anchor::{Account, Accounts, Signer};
+mod common;
+
+use common::{account_data, create_account_info};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{declare_id, system_program, Account, Accounts, Key, Signer, UncheckedAccount};
 use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
 
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+const ADMIN: Pubkey = Pubkey::new_from_array([7u8; 32]);
+
 #[mini_anchor::account]
 pub struct Counter {
     pub authority: Pubkey,
@@ -14,34 +22,40 @@ pub struct Initialize<'info> {
     pub authority: Signer<'info>,
 }
 
-fn create_account_info<'a>(
-    key: &'a Pubkey,
-    is_signer: bool,
-    is_writeable: bool,
-    lamports: &'a mut u64,
-    data: &'a mut [u8],
-    owner: &'a Pubkey,
-) -> AccountInfo<'a> {
-    AccountInfo::new(
-        key,
-        is_signer,
-        is_writeable,
-        lamports,
-        data,
-        owner,
-        false,
-        0,
-    )
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut, has_one = authority)]
+    pub counter: Account<'info, Counter>,
+    #[account(owner = system_program::ID)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdminOnly<'info> {
+    #[account(address = ADMIN)]
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Unchecked<'info> {
+    /// CHECK: only used as a lamport destination
+    pub destination: UncheckedAccount<'info>,
+    /// CHECK: read-only metadata, never deserialized
+    #[account(owner = system_program::ID)]
+    pub metadata: AccountInfo<'info>,
 }
 
 #[test]
 fn test_try_accounts_success() {
-    let program_id = Pubkey::new_unique();
+    let program_id = ID;
     let counter_key = Pubkey::new_unique();
     let authority_key = Pubkey::new_unique();
 
     let mut counter_lamports = 1000u64;
-    let mut counter_data = vec![0u8; Counter::SPACE];
+    let mut counter_data = account_data(Counter {
+        authority: authority_key,
+        count: 0,
+    });
     let mut authority_lamports = 1000u64;
     let mut authority_data = vec![0u8; 0];
 
@@ -66,21 +80,33 @@ fn test_try_accounts_success() {
     let accounts = vec![counter_info, authority_info];
 
     // Test try_accounts
-    let result = Initialize::try_accounts(&accounts);
+    let result = Initialize::try_accounts(
+        &program_id,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    );
     assert!(result.is_ok());
 
     let ctx = result.unwrap();
-    assert_eq!(ctx.counter.key(), &counter_key);
-    assert_eq!(ctx.authority.key(), &authority_key);
+    assert_eq!(ctx.counter.key(), counter_key);
+    assert_eq!(ctx.authority.key(), authority_key);
+
+    // Account data is deserialized up front and reachable through Deref
+    assert_eq!(ctx.counter.authority, authority_key);
+    assert_eq!(ctx.counter.count, 0);
 }
 
 #[test]
 fn test_try_accounts_not_enough_accounts() {
-    let program_id = Pubkey::new_unique();
+    let program_id = ID;
     let counter_key = Pubkey::new_unique();
 
     let mut counter_lamports = 1000u64;
-    let mut counter_data = vec![0u8; Counter::SPACE];
+    let mut counter_data = account_data(Counter {
+        authority: Pubkey::new_unique(),
+        count: 0,
+    });
 
     let counter_info = create_account_info(
         &counter_key,
@@ -94,19 +120,28 @@ fn test_try_accounts_not_enough_accounts() {
     // Only one account, but Initialize needs 2
     let accounts = vec![counter_info];
 
-    let result = Initialize::try_accounts(&accounts);
+    let result = Initialize::try_accounts(
+        &program_id,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    );
     assert!(result.is_err());
+    assert_eq!(result.err(), Some(ErrorCode::AccountNotEnoughKeys.into()));
 }
 
 #[test]
 fn test_try_accounts_missing_signer() {
-    let program_id = Pubkey::new_unique();
+    let program_id = ID;
     let counter_key = Pubkey::new_unique();
     let authority_key = Pubkey::new_unique();
 
     let mut counter_lamports = 1000u64;
     let mut authority_lamports = 1000u64;
-    let mut counter_data = vec![0u8; Counter::SPACE];
+    let mut counter_data = account_data(Counter {
+        authority: Pubkey::new_unique(),
+        count: 0,
+    });
     let mut authority_data = vec![0u8; 0];
 
     let counter_info = create_account_info(
@@ -129,18 +164,27 @@ fn test_try_accounts_missing_signer() {
 
     let accounts = vec![counter_info, authority_info];
 
-    let result = Initialize::try_accounts(&accounts);
+    let result = Initialize::try_accounts(
+        &program_id,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    );
     assert!(result.is_err()); // Should fail due to missing signer
+    assert_eq!(result.err(), Some(ErrorCode::AccountNotSigner.into()));
 }
 
 #[test]
 fn test_try_accounts_not_writeable() {
-    let program_id = Pubkey::new_unique();
+    let program_id = ID;
     let counter_key = Pubkey::new_unique();
     let authority_key = Pubkey::new_unique();
 
     let mut counter_lamports = 1000u64;
-    let mut counter_data = vec![0u8; Counter::SPACE];
+    let mut counter_data = account_data(Counter {
+        authority: Pubkey::new_unique(),
+        count: 0,
+    });
     let mut authority_lamports = 1000u64;
     let mut authority_data = vec![0u8; 0];
 
@@ -164,6 +208,384 @@ fn test_try_accounts_not_writeable() {
 
     let accounts = vec![counter_info, authority_info];
 
-    let result = Initialize::try_accounts(&accounts);
+    let result = Initialize::try_accounts(
+        &program_id,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    );
     assert!(result.is_err()); // Should fail - counter not writeable
+    assert_eq!(result.err(), Some(ErrorCode::ConstraintMut.into()));
+}
+
+#[test]
+fn test_try_accounts_leaves_remaining_accounts() {
+    let program_id = ID;
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+    let extra_key = Pubkey::new_unique();
+
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(Counter {
+        authority: Pubkey::new_unique(),
+        count: 0,
+    });
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut extra_lamports = 1000u64;
+    let mut extra_data = vec![0u8; 0];
+
+    let counter_info = create_account_info(
+        &counter_key,
+        false,
+        true,
+        &mut counter_lamports,
+        &mut counter_data,
+        &program_id,
+    );
+
+    let authority_info = create_account_info(
+        &authority_key,
+        true,
+        false,
+        &mut authority_lamports,
+        &mut authority_data,
+        &program_id,
+    );
+
+    let extra_info = create_account_info(
+        &extra_key,
+        false,
+        false,
+        &mut extra_lamports,
+        &mut extra_data,
+        &program_id,
+    );
+
+    let accounts = vec![counter_info, authority_info, extra_info];
+    let mut remaining = accounts.as_slice();
+
+    let result = Initialize::try_accounts(
+        &program_id,
+        &mut remaining,
+        &[],
+        &mut InitializeBumps::default(),
+    );
+    assert!(result.is_ok());
+
+    // Only the undeclared account is left over
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].key, &extra_key);
+}
+
+#[test]
+fn test_try_accounts_wrong_owner() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+    let fake_program_id = Pubkey::new_unique();
+
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(Counter {
+        authority: authority_key,
+        count: 0,
+    });
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    // Valid Counter layout, but owned by another program
+    let counter_info = create_account_info(
+        &counter_key,
+        false,
+        true,
+        &mut counter_lamports,
+        &mut counter_data,
+        &fake_program_id,
+    );
+
+    let authority_info = create_account_info(
+        &authority_key,
+        true,
+        false,
+        &mut authority_lamports,
+        &mut authority_data,
+        &ID,
+    );
+
+    let accounts = vec![counter_info, authority_info];
+
+    let result = Initialize::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    );
+    assert_eq!(
+        result.err(),
+        Some(ErrorCode::AccountOwnedByWrongProgram.into())
+    );
+}
+
+#[test]
+fn test_try_accounts_wrong_discriminator() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = vec![0u8; Counter::SPACE];
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let counter_info = create_account_info(
+        &counter_key,
+        false,
+        true,
+        &mut counter_lamports,
+        &mut counter_data,
+        &ID,
+    );
+
+    let authority_info = create_account_info(
+        &authority_key,
+        true,
+        false,
+        &mut authority_lamports,
+        &mut authority_data,
+        &ID,
+    );
+
+    let accounts = vec![counter_info, authority_info];
+
+    let result = Initialize::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    );
+    assert_eq!(
+        result.err(),
+        Some(ErrorCode::AccountDiscriminatorMismatch.into())
+    );
+}
+
+#[test]
+fn test_has_one() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(Counter {
+        authority: authority_key,
+        count: 0,
+    });
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let counter_info = create_account_info(
+        &counter_key,
+        false,
+        true,
+        &mut counter_lamports,
+        &mut counter_data,
+        &ID,
+    );
+
+    let authority_info = create_account_info(
+        &authority_key,
+        true,
+        false,
+        &mut authority_lamports,
+        &mut authority_data,
+        &system_program::ID,
+    );
+
+    let accounts = vec![counter_info, authority_info];
+
+    let result = Update::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut UpdateBumps::default(),
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_has_one_mismatch() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+
+    let mut counter_lamports = 1000u64;
+    // Counter belongs to someone else
+    let mut counter_data = account_data(Counter {
+        authority: Pubkey::new_unique(),
+        count: 0,
+    });
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let counter_info = create_account_info(
+        &counter_key,
+        false,
+        true,
+        &mut counter_lamports,
+        &mut counter_data,
+        &ID,
+    );
+
+    let authority_info = create_account_info(
+        &authority_key,
+        true,
+        false,
+        &mut authority_lamports,
+        &mut authority_data,
+        &system_program::ID,
+    );
+
+    let accounts = vec![counter_info, authority_info];
+
+    let result = Update::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut UpdateBumps::default(),
+    );
+    assert_eq!(result.err(), Some(ErrorCode::ConstraintHasOne.into()));
+}
+
+#[test]
+fn test_owner_mismatch() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(Counter {
+        authority: authority_key,
+        count: 0,
+    });
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let counter_info = create_account_info(
+        &counter_key,
+        false,
+        true,
+        &mut counter_lamports,
+        &mut counter_data,
+        &ID,
+    );
+
+    // Signer is not a system-owned wallet
+    let authority_info = create_account_info(
+        &authority_key,
+        true,
+        false,
+        &mut authority_lamports,
+        &mut authority_data,
+        &ID,
+    );
+
+    let accounts = vec![counter_info, authority_info];
+
+    let result = Update::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut UpdateBumps::default(),
+    );
+    assert_eq!(result.err(), Some(ErrorCode::ConstraintOwner.into()));
+}
+
+#[test]
+fn test_address() {
+    let mut admin_lamports = 1000u64;
+    let mut admin_data = vec![0u8; 0];
+
+    let admin_info = create_account_info(
+        &ADMIN,
+        true,
+        false,
+        &mut admin_lamports,
+        &mut admin_data,
+        &system_program::ID,
+    );
+
+    let accounts = vec![admin_info];
+
+    let result = AdminOnly::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut AdminOnlyBumps::default(),
+    );
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_address_mismatch() {
+    let admin_key = Pubkey::new_unique();
+    let mut admin_lamports = 1000u64;
+    let mut admin_data = vec![0u8; 0];
+
+    let admin_info = create_account_info(
+        &admin_key,
+        true,
+        false,
+        &mut admin_lamports,
+        &mut admin_data,
+        &system_program::ID,
+    );
+
+    let accounts = vec![admin_info];
+
+    let result = AdminOnly::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut AdminOnlyBumps::default(),
+    );
+    assert_eq!(result.err(), Some(ErrorCode::ConstraintAddress.into()));
+}
+
+#[test]
+fn test_unchecked_accounts() {
+    let destination_key = Pubkey::new_unique();
+    let metadata_key = Pubkey::new_unique();
+    let other_program = Pubkey::new_unique();
+
+    let mut destination_lamports = 0u64;
+    let mut destination_data = vec![1u8; 3];
+    let mut metadata_lamports = 1000u64;
+    let mut metadata_data = vec![0u8; 0];
+
+    // Neither signed, writable nor owned by this program
+    let destination_info = create_account_info(
+        &destination_key,
+        false,
+        false,
+        &mut destination_lamports,
+        &mut destination_data,
+        &other_program,
+    );
+
+    let metadata_info = create_account_info(
+        &metadata_key,
+        false,
+        false,
+        &mut metadata_lamports,
+        &mut metadata_data,
+        &system_program::ID,
+    );
+
+    let accounts = vec![destination_info, metadata_info];
+
+    let ctx = Unchecked::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut UncheckedBumps::default(),
+    )
+    .unwrap();
+    assert_eq!(ctx.destination.key(), destination_key);
+    assert_eq!(ctx.destination.data_len(), 3);
+    assert_eq!(ctx.metadata.key, &metadata_key);
 }
@@ -1,6 +1,8 @@
-use mini_anchor::account;
+use mini_anchor::{account, declare_id};
 use solana_program::pubkey::Pubkey;
 
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
 #[account]
 pub struct UserProfile {
     pub authority: Pubkey,
@@ -20,6 +22,13 @@ fn test_discriminator() {
     assert_eq!(UserProfile::DISCRIMINATOR.len(), 8);
 }
 
+#[test]
+fn test_owner() {
+    use mini_anchor::Owner;
+
+    assert_eq!(UserProfile::owner(), ID);
+}
+
 #[test]
 fn test_roundtrip() {
     let user_profile = UserProfile {
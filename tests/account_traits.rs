@@ -0,0 +1,171 @@
+mod common;
+
+use common::{account_data, create_account_info};
+use mini_anchor::system_program::{self, System};
+use mini_anchor::{
+    declare_id, Account, Accounts, Key, Program, Signer, ToAccountInfo, ToAccountInfos,
+    UncheckedAccount,
+};
+use solana_program::pubkey::Pubkey;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Owners<'info> {
+    pub authority: Signer<'info>,
+    pub delegate: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct Transfer<'info> {
+    pub owners: Owners<'info>,
+    #[account(mut)]
+    pub counter: Box<Account<'info, Counter>>,
+    /// CHECK: only its key is read
+    pub destination: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Written against the traits only, as CPI helpers would be
+fn keys<'info>(accounts: &impl ToAccountInfos<'info>) -> Vec<Pubkey> {
+    accounts
+        .to_account_infos()
+        .iter()
+        .map(|info| *info.key)
+        .collect()
+}
+
+fn info_key<'info>(account: &(impl ToAccountInfo<'info> + Key)) -> Pubkey {
+    assert_eq!(*account.to_account_info().key, account.key());
+    account.key()
+}
+
+#[test]
+fn test_account_traits() {
+    let authority_key = Pubkey::new_unique();
+    let delegate_key = Pubkey::new_unique();
+    let counter_key = Pubkey::new_unique();
+    let destination_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut delegate_lamports = 1000u64;
+    let mut delegate_data = vec![0u8; 0];
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(Counter { count: 0 });
+    let mut destination_lamports = 0u64;
+    let mut destination_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &delegate_key,
+            true,
+            false,
+            &mut delegate_lamports,
+            &mut delegate_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &destination_key,
+            false,
+            false,
+            &mut destination_lamports,
+            &mut destination_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let ctx = Transfer::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut TransferBumps::default(),
+    )
+    .unwrap();
+
+    assert_eq!(info_key(&ctx.owners.authority), authority_key);
+    assert_eq!(info_key(ctx.counter.as_ref()), counter_key);
+    assert_eq!(info_key(&ctx.destination), destination_key);
+    assert_eq!(info_key(&ctx.system_program), system_program_id);
+    assert_eq!(info_key(&accounts[0]), authority_key);
+
+    // Whole structs flatten to their accounts in declaration order
+    assert_eq!(
+        keys(&ctx),
+        vec![
+            authority_key,
+            delegate_key,
+            counter_key,
+            destination_key,
+            system_program_id
+        ]
+    );
+    assert_eq!(keys(&ctx.counter), vec![counter_key]);
+}
+
+#[test]
+fn test_absent_optional_account_infos() {
+    let authority_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut placeholder_lamports = 1u64;
+    let mut placeholder_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+        // The program id stands in for the missing delegate
+        create_account_info(
+            &ID,
+            false,
+            false,
+            &mut placeholder_lamports,
+            &mut placeholder_data,
+            &system_program_id,
+        ),
+    ];
+
+    let owners = Owners::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut OwnersBumps::default(),
+    )
+    .unwrap();
+
+    assert!(owners.delegate.is_none());
+    assert_eq!(keys(&owners), vec![authority_key]);
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use common::{account_data, create_account_info};
+use mini_anchor::common::is_closed;
+use mini_anchor::error::ErrorCode;
+use mini_anchor::system_program;
+use mini_anchor::{declare_id, Account, Accounts, AccountsExit, Signer};
+use solana_program::pubkey::Pubkey;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Close<'info> {
+    #[account(mut, close = authority, has_one = authority)]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+// Opts out of the duplicate check, leaving the close check to catch it
+#[derive(Accounts)]
+pub struct CloseDup<'info> {
+    #[account(mut, close = authority, has_one = authority, dup = authority)]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Read<'info> {
+    pub counter: Account<'info, Counter>,
+}
+
+#[test]
+fn test_close_on_exit() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+
+    // Owners are written through by `assign`, so they must not be constants
+    let counter_owner = ID;
+    let authority_owner = system_program::ID;
+
+    let mut counter_lamports = 5000u64;
+    let mut counter_data = account_data(Counter {
+        authority: authority_key,
+        count: 7,
+    });
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &counter_owner,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            true,
+            &mut authority_lamports,
+            &mut authority_data,
+            &authority_owner,
+        ),
+    ];
+
+    let mut ctx = Close::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut CloseBumps::default(),
+    )
+    .unwrap();
+
+    // Changes made before closing are discarded
+    ctx.counter.count = 8;
+    ctx.exit(&ID).unwrap();
+
+    assert_eq!(accounts[0].lamports(), 0);
+    assert_eq!(accounts[1].lamports(), 6000);
+    assert_eq!(accounts[0].owner, &system_program::ID);
+    assert!(accounts[0].data.borrow().iter().all(|byte| *byte == 0));
+    assert!(is_closed(&accounts[0]));
+    assert!(!is_closed(&accounts[1]));
+
+    // A closed account cannot be loaded again, even if it is refunded
+    **accounts[0].lamports.borrow_mut() = 5000;
+    let result = Read::try_accounts(&ID, &mut &accounts[..1], &[], &mut ReadBumps::default());
+    assert_eq!(
+        result.err(),
+        Some(ErrorCode::AccountOwnedByWrongProgram.into())
+    );
+}
+
+#[test]
+fn test_close_into_itself() {
+    let key = Pubkey::new_unique();
+    let counter_owner = ID;
+    let authority_owner = system_program::ID;
+
+    let mut counter_lamports = 5000u64;
+    let mut counter_data = account_data(Counter {
+        authority: key,
+        count: 0,
+    });
+    let mut authority_lamports = 5000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    // Same key passed as both the account and the destination
+    let accounts = vec![
+        create_account_info(
+            &key,
+            true,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &counter_owner,
+        ),
+        create_account_info(
+            &key,
+            true,
+            true,
+            &mut authority_lamports,
+            &mut authority_data,
+            &authority_owner,
+        ),
+    ];
+
+    let result = Close::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut CloseBumps::default(),
+    );
+    assert_eq!(
+        result.err(),
+        Some(ErrorCode::ConstraintDuplicateMutableAccount.into())
+    );
+
+    let result = CloseDup::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut CloseDupBumps::default(),
+    );
+    assert_eq!(result.err(), Some(ErrorCode::ConstraintClose.into()));
+}
@@ -0,0 +1,228 @@
+// Syscall stubs standing in for the runtime when tests run off-chain
+#![allow(dead_code)]
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use mini_anchor::{system_program, Accounts, AccountsExit, AnchorSerialize, Space};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE, SUCCESS},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+};
+
+use std::cell::RefCell;
+use std::slice;
+use std::sync::Once;
+
+static NATIVE_LOADER: Pubkey =
+    solana_program::pubkey!("NativeLoader1111111111111111111111111111111");
+
+struct TestSyscallStubs;
+
+thread_local! {
+    // `sol_log_data` entries of the current test, see `take_data_logs`
+    static DATA_LOGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+impl SyscallStubs for TestSyscallStubs {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    // Recorded the way the runtime writes them to the transaction logs
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<_> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+        let log = format!("Program data: {}", fields.join(" "));
+        DATA_LOGS.with(|logs| logs.borrow_mut().push(log));
+    }
+
+    // Minimal System Program. Account data cannot grow off-chain, so tests
+    // pre-size data buffers to the space being allocated.
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id != system_program::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        // Required signers either signed the transaction or are PDAs of the
+        // calling program (the test crate's ID) signed for with their seeds
+        for meta in instruction.accounts.iter().filter(|meta| meta.is_signer) {
+            let signed = account_infos
+                .iter()
+                .any(|info| *info.key == meta.pubkey && info.is_signer);
+            let pda_signed = signers_seeds.iter().any(|seeds| {
+                Pubkey::create_program_address(seeds, &crate::ID)
+                    .is_ok_and(|key| key == meta.pubkey)
+            });
+            if !signed && !pda_signed {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+        }
+
+        let account = |index: usize| {
+            let key = instruction.accounts[index].pubkey;
+            account_infos
+                .iter()
+                .find(|info| *info.key == key)
+                .ok_or(ProgramError::NotEnoughAccountKeys)
+        };
+
+        let data = &instruction.data;
+        let read_u64 =
+            |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        let read_pubkey = |offset: usize| Pubkey::try_from(&data[offset..offset + 32]).unwrap();
+
+        match u32::from_le_bytes(data[..4].try_into().unwrap()) {
+            // CreateAccount { lamports, space, owner }
+            0 => {
+                transfer(account(0)?, account(1)?, read_u64(4))?;
+                check_space(account(1)?, read_u64(12))?;
+                account(1)?.assign(&read_pubkey(20));
+            }
+            // Assign { owner }
+            1 => account(0)?.assign(&read_pubkey(4)),
+            // Transfer { lamports }
+            2 => transfer(account(0)?, account(1)?, read_u64(4))?,
+            // Allocate { space }
+            8 => check_space(account(0)?, read_u64(4))?,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        }
+
+        Ok(())
+    }
+}
+
+fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) -> ProgramResult {
+    let mut from_lamports = from.try_borrow_mut_lamports()?;
+    **from_lamports = from_lamports
+        .checked_sub(lamports)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    **to.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+fn check_space(info: &AccountInfo, space: u64) -> ProgramResult {
+    if info.data_len() as u64 != space {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+pub fn setup_syscall_stubs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        set_syscall_stubs(Box::new(TestSyscallStubs));
+    });
+}
+
+// `Program data:` lines logged on this thread since the last call
+pub fn take_data_logs() -> Vec<String> {
+    DATA_LOGS.with(|logs| logs.take())
+}
+
+pub fn create_account_info<'a>(
+    key: &'a Pubkey,
+    is_signer: bool,
+    is_writeable: bool,
+    lamports: &'a mut u64,
+    data: &'a mut [u8],
+    owner: &'a Pubkey,
+) -> AccountInfo<'a> {
+    AccountInfo::new(
+        key,
+        is_signer,
+        is_writeable,
+        lamports,
+        data,
+        owner,
+        false,
+        0,
+    )
+}
+
+// Serialized `#[account]` data, sized to the account's maximum space
+pub fn account_data<T: AnchorSerialize + Space>(account: T) -> Vec<u8> {
+    let mut data = vec![0u8; T::SPACE];
+    account.serialize(&mut data).unwrap();
+    data
+}
+
+// Runs an instruction the way the entrypoint does: loads `T` from `accounts`,
+// hands it to `handler` and exits. The caller reads back the account data.
+pub fn run_accounts<'info, T>(
+    accounts: &mut &'info [AccountInfo<'info>],
+    bumps: &mut T::Bumps,
+    handler: impl FnOnce(&mut T),
+) -> Result<T, ProgramError>
+where
+    T: Accounts<'info> + AccountsExit<'info>,
+{
+    let mut ctx = T::try_accounts(&crate::ID, accounts, &[], bumps)?;
+    handler(&mut ctx);
+    ctx.exit(&crate::ID)?;
+    Ok(ctx)
+}
+
+// Executable account, as `Program<'info, T>` fields expect
+pub fn program_account_info<'a>(
+    key: &'a Pubkey,
+    lamports: &'a mut u64,
+    data: &'a mut [u8],
+) -> AccountInfo<'a> {
+    AccountInfo::new(key, false, false, lamports, data, &NATIVE_LOADER, true, 0)
+}
+
+pub fn minimum_balance(space: usize) -> u64 {
+    Rent::default().minimum_balance(space)
+}
+
+// Account memory laid out like the runtime's serialized input, which
+// `AccountInfo::realloc` relies on: the original data length sits just before
+// the key, the current length just before the data, and there is room for the
+// data to grow by MAX_PERMITTED_DATA_INCREASE.
+#[repr(C)]
+struct SerializedKey {
+    original_data_len: u32,
+    key: Pubkey,
+}
+
+pub struct SerializedAccount {
+    key: SerializedKey,
+    // u64 words keep the length prefix aligned
+    buffer: Vec<u64>,
+}
+
+impl SerializedAccount {
+    pub fn new(key: Pubkey, data: &[u8]) -> Self {
+        let words = 1 + (data.len() + MAX_PERMITTED_DATA_INCREASE).div_ceil(8);
+        let mut buffer = vec![0u64; words];
+        buffer[0] = data.len() as u64;
+
+        let bytes = unsafe { slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut u8, words * 8) };
+        bytes[8..8 + data.len()].copy_from_slice(data);
+
+        Self {
+            key: SerializedKey {
+                original_data_len: data.len() as u32,
+                key,
+            },
+            buffer,
+        }
+    }
+
+    // Key and data to build an `AccountInfo` from
+    pub fn parts(&mut self) -> (&Pubkey, &mut [u8]) {
+        let len = self.buffer[0] as usize;
+        let data =
+            unsafe { slice::from_raw_parts_mut((self.buffer.as_mut_ptr() as *mut u8).add(8), len) };
+        (&self.key.key, data)
+    }
+}
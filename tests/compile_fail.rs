@@ -0,0 +1,7 @@
+// Pins the diagnostics of `#[account]`, `#[derive(Accounts)]` and `declare_id!`.
+// Regenerate the `.stderr` files with `TRYBUILD=overwrite cargo test --test compile_fail`.
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
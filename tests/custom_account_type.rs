@@ -0,0 +1,238 @@
+mod common;
+
+use common::{account_data, create_account_info, run_accounts};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{
+    declare_id, Account, Accounts, AccountsExit, Bumps, Key, Signer, ToAccountInfos,
+    UncheckedAccount,
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+// An account wrapper defined outside the framework, named like a framework type
+pub struct AccountBook<'info> {
+    pub info: &'info AccountInfo<'info>,
+}
+
+impl<'info> Accounts<'info> for AccountBook<'info> {
+    fn try_accounts(
+        _program_id: &Pubkey,
+        accounts: &mut &'info [AccountInfo<'info>],
+        _ix_data: &[u8],
+        _bumps: &mut (),
+    ) -> Result<Self, ProgramError> {
+        let (info, rest) = accounts
+            .split_first()
+            .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+        if info.lamports() == 0 {
+            return Err(ProgramError::UninitializedAccount);
+        }
+        *accounts = rest;
+        Ok(Self { info })
+    }
+}
+
+impl Bumps for AccountBook<'_> {
+    type Bumps = ();
+}
+
+impl<'info> AccountsExit<'info> for AccountBook<'info> {}
+
+impl<'info> AsRef<AccountInfo<'info>> for AccountBook<'info> {
+    fn as_ref(&self) -> &AccountInfo<'info> {
+        self.info
+    }
+}
+
+impl<'info> ToAccountInfos<'info> for AccountBook<'info> {
+    fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        vec![self.info.clone()]
+    }
+}
+
+pub type Authority<'info> = Signer<'info>;
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+pub type CounterAccount<'info> = Account<'info, Counter>;
+
+#[derive(Accounts)]
+pub struct Record<'info> {
+    pub book: AccountBook<'info>,
+    pub authority: Authority<'info>,
+    /// CHECK: only its key is read
+    pub witness: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Tally<'info> {
+    #[account(mut, has_one = authority)]
+    pub counter: CounterAccount<'info>,
+    pub snapshot: CounterAccount<'info>,
+    pub authority: Authority<'info>,
+}
+
+// Runs try_accounts and exit on a book with the given lamports
+fn try_record(book_lamports: u64, authority_signed: bool) -> Result<(), ProgramError> {
+    let book_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+    let witness_key = Pubkey::new_unique();
+    let system_program_id = solana_program::system_program::ID;
+
+    let mut book_lamports = book_lamports;
+    let mut book_data = vec![0u8; 16];
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut witness_lamports = 0u64;
+    let mut witness_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &book_key,
+            false,
+            false,
+            &mut book_lamports,
+            &mut book_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority_key,
+            authority_signed,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &witness_key,
+            false,
+            false,
+            &mut witness_lamports,
+            &mut witness_data,
+            &system_program_id,
+        ),
+    ];
+
+    let ctx = run_accounts::<Record>(
+        &mut accounts.as_slice(),
+        &mut RecordBumps::default(),
+        |_| {},
+    )?;
+
+    assert_eq!(ctx.to_account_infos().len(), 3);
+    assert_eq!(ctx.book.key(), book_key);
+    assert_eq!(ctx.authority.key(), authority_key);
+    Ok(())
+}
+
+#[test]
+fn test_custom_account_type() {
+    try_record(1000, true).unwrap();
+}
+
+#[test]
+fn test_custom_account_type_checks() {
+    let result = try_record(0, true);
+    assert_eq!(result.err(), Some(ProgramError::UninitializedAccount));
+}
+
+#[test]
+fn test_type_alias_keeps_checks() {
+    let result = try_record(1000, false);
+    assert_eq!(result.err(), Some(ErrorCode::AccountNotSigner.into()));
+}
+
+// Runs `Tally` with counters at the given keys, letting `handler` change the
+// loaded accounts before exiting, and returns the stored counts
+fn try_tally(
+    counter_key: Pubkey,
+    snapshot_key: Pubkey,
+    handler: fn(&mut Tally),
+) -> (Result<(), ProgramError>, [u64; 2]) {
+    let authority_key = Pubkey::new_unique();
+    let system_program_id = solana_program::system_program::ID;
+
+    let counter = || Counter {
+        authority: authority_key,
+        count: 1,
+    };
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(counter());
+    let mut snapshot_lamports = 1000u64;
+    let mut snapshot_data = account_data(counter());
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &snapshot_key,
+            false,
+            false,
+            &mut snapshot_lamports,
+            &mut snapshot_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+    ];
+
+    let result = run_accounts(
+        &mut accounts.as_slice(),
+        &mut TallyBumps::default(),
+        handler,
+    )
+    .map(|_| ());
+
+    let counts = [0, 1].map(|i| {
+        Counter::try_deserialize(&accounts[i].data.borrow())
+            .unwrap()
+            .count
+    });
+    (result, counts)
+}
+
+#[test]
+fn test_aliased_account_constraints() {
+    let (counter_key, snapshot_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    // The `mut` alias is written back
+    assert_eq!(
+        try_tally(counter_key, snapshot_key, |ctx| ctx.counter.count = 5),
+        (Ok(()), [5, 1])
+    );
+
+    // Changing the read-only alias is rejected rather than dropped
+    assert_eq!(
+        try_tally(counter_key, snapshot_key, |ctx| ctx.snapshot.count = 5),
+        (Err(ErrorCode::AccountNotMutable.into()), [1, 1])
+    );
+}
+
+#[test]
+fn test_aliased_account_duplicate() {
+    let shared = Pubkey::new_unique();
+    let (result, _) = try_tally(shared, shared, |_| {});
+    assert_eq!(
+        result,
+        Err(ErrorCode::ConstraintDuplicateMutableAccount.into())
+    );
+}
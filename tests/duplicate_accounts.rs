@@ -0,0 +1,148 @@
+mod common;
+
+use common::{account_data, create_account_info, run_accounts};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{declare_id, Account, Accounts};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Transfer<'info> {
+    #[account(mut)]
+    pub from: Account<'info, Counter>,
+    #[account(mut)]
+    pub to: Account<'info, Counter>,
+    pub source: Account<'info, Counter>,
+    pub reference: Account<'info, Counter>,
+}
+
+#[derive(Accounts)]
+pub struct TransferToSelf<'info> {
+    #[account(mut, dup = to)]
+    pub from: Account<'info, Counter>,
+    #[account(mut)]
+    pub to: Account<'info, Counter>,
+    pub source: Account<'info, Counter>,
+    pub reference: Account<'info, Counter>,
+}
+
+#[derive(Accounts)]
+pub struct Mirror<'info> {
+    #[account(mut, dup = mirror)]
+    pub counter: Account<'info, Counter>,
+    pub mirror: Account<'info, Counter>,
+}
+
+// Runs try_accounts for both structs with counters at the given keys,
+// in field order: from, to, source, reference
+fn try_transfer(keys: [Pubkey; 4]) -> (Result<(), ProgramError>, Result<(), ProgramError>) {
+    let mut lamports = [1000u64; 4];
+    let mut data: Vec<Vec<u8>> = (0..4).map(|_| account_data(Counter { count: 0 })).collect();
+
+    let accounts: Vec<_> = keys
+        .iter()
+        .zip(lamports.iter_mut())
+        .zip(data.iter_mut())
+        .map(|((key, lamports), data)| create_account_info(key, false, true, lamports, data, &ID))
+        .collect();
+
+    let transfer = Transfer::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut TransferBumps::default(),
+    )
+    .map(|_| ());
+    let transfer_to_self = TransferToSelf::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut TransferToSelfBumps::default(),
+    )
+    .map(|_| ());
+    (transfer, transfer_to_self)
+}
+
+#[test]
+fn test_distinct_accounts() {
+    let keys = [(); 4].map(|_| Pubkey::new_unique());
+    assert_eq!(try_transfer(keys), (Ok(()), Ok(())));
+}
+
+#[test]
+fn test_duplicate_mutable_accounts() {
+    let shared = Pubkey::new_unique();
+    let keys = [shared, shared, Pubkey::new_unique(), Pubkey::new_unique()];
+
+    // Allowed only where `dup` says so
+    let (transfer, transfer_to_self) = try_transfer(keys);
+    assert_eq!(
+        transfer,
+        Err(ErrorCode::ConstraintDuplicateMutableAccount.into())
+    );
+    assert_eq!(transfer_to_self, Ok(()));
+}
+
+#[test]
+fn test_mutable_account_passed_as_read_only() {
+    let shared = Pubkey::new_unique();
+    let keys = [Pubkey::new_unique(), shared, shared, Pubkey::new_unique()];
+
+    let (transfer, transfer_to_self) = try_transfer(keys);
+    assert_eq!(
+        transfer,
+        Err(ErrorCode::ConstraintDuplicateMutableAccount.into())
+    );
+    assert_eq!(
+        transfer_to_self,
+        Err(ErrorCode::ConstraintDuplicateMutableAccount.into())
+    );
+}
+
+#[test]
+fn test_duplicate_read_only_accounts() {
+    let shared = Pubkey::new_unique();
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique(), shared, shared];
+
+    assert_eq!(try_transfer(keys), (Ok(()), Ok(())));
+}
+
+// Runs `Mirror` with both fields holding the same account, letting `handler`
+// change the loaded accounts before exiting, and returns the stored count
+fn try_mirror(handler: fn(&mut Mirror)) -> (Result<(), ProgramError>, u64) {
+    let key = Pubkey::new_unique();
+    let mut lamports = 1000u64;
+    let mut data = account_data(Counter { count: 1 });
+
+    // The runtime hands out one `AccountInfo` per slot, sharing the same data
+    let info = create_account_info(&key, false, true, &mut lamports, &mut data, &ID);
+    let accounts = vec![info.clone(), info];
+
+    let result = run_accounts(
+        &mut accounts.as_slice(),
+        &mut MirrorBumps::default(),
+        handler,
+    )
+    .map(|_| ());
+
+    let counter = Counter::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    (result, counter.count)
+}
+
+#[test]
+fn test_duplicate_accounts_exit() {
+    // The read-only copy is stale once the mutable one is written back
+    assert_eq!(try_mirror(|ctx| ctx.counter.count = 5), (Ok(()), 5));
+
+    // Changing the read-only copy is still rejected
+    assert_eq!(
+        try_mirror(|ctx| ctx.mirror.count = 5),
+        (Err(ErrorCode::AccountNotMutable.into()), 1)
+    );
+}
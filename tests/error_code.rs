@@ -0,0 +1,75 @@
+use mini_anchor::error::ErrorCode;
+use mini_anchor::error_code;
+use solana_program::program_error::ProgramError;
+
+#[error_code]
+pub enum CounterError {
+    #[msg("Counter would overflow")]
+    Overflow,
+    #[msg("Only the authority can reset the counter")]
+    Unauthorized,
+    Frozen,
+}
+
+#[error_code(offset = 9000)]
+pub enum VaultError {
+    #[msg("Vault is empty")]
+    Empty,
+    #[msg("Vault is locked")]
+    Locked = 10,
+    Closed,
+}
+
+#[test]
+fn test_default_offset() {
+    assert_eq!(CounterError::OFFSET, 6000);
+    assert_eq!(CounterError::Overflow.code(), 6000);
+    assert_eq!(CounterError::Unauthorized.code(), 6001);
+    assert_eq!(CounterError::Frozen.code(), 6002);
+}
+
+#[test]
+fn test_custom_offset_and_discriminants() {
+    assert_eq!(VaultError::Empty.code(), 9000);
+    assert_eq!(VaultError::Locked.code(), 9010);
+    assert_eq!(VaultError::Closed.code(), 9011);
+}
+
+#[test]
+fn test_messages() {
+    assert_eq!(CounterError::Overflow.message(), "Counter would overflow");
+    assert_eq!(CounterError::Overflow.name(), "Overflow");
+    // Falls back to the variant name without #[msg]
+    assert_eq!(CounterError::Frozen.message(), "Frozen");
+    assert_eq!(VaultError::Locked.to_string(), "Vault is locked");
+}
+
+#[test]
+fn test_into_program_error() {
+    let error: ProgramError = CounterError::Unauthorized.into();
+    assert_eq!(error, ProgramError::Custom(6001));
+
+    let error: ProgramError = VaultError::Locked.into();
+    assert_eq!(error, ProgramError::Custom(9010));
+}
+
+#[test]
+fn test_from_code() {
+    assert_eq!(CounterError::from_code(6000), Some(CounterError::Overflow));
+    assert_eq!(CounterError::from_code(6002), Some(CounterError::Frozen));
+    assert_eq!(CounterError::from_code(6003), None);
+    assert_eq!(CounterError::from_code(39), None);
+    assert_eq!(VaultError::from_code(9011), Some(VaultError::Closed));
+    assert_eq!(VaultError::from_code(9001), None);
+}
+
+#[test]
+fn test_framework_error_codes() {
+    assert_eq!(ErrorCode::InstructionMissing.code(), 100);
+    assert_eq!(ErrorCode::ConstraintMut.code(), 2000);
+    assert_eq!(ErrorCode::AccountNotSigner.code(), 3010);
+    assert_eq!(
+        ErrorCode::from_code(3005),
+        Some(ErrorCode::AccountNotEnoughKeys)
+    );
+}
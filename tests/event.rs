@@ -0,0 +1,112 @@
+mod common;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use mini_anchor::event::parse_events;
+use mini_anchor::{declare_id, emit, event, AnchorSerialize, Discriminator, Space};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[event]
+pub struct Deposited {
+    pub vault: Pubkey,
+    pub amount: u64,
+    #[max_len(16)]
+    pub memo: String,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub amount: u64,
+}
+
+// Wraps logged data in the invoke and success lines of `program_id`
+fn transaction_logs(program_id: &Pubkey, data_logs: Vec<String>) -> Vec<String> {
+    let mut logs = vec![format!("Program {} invoke [1]", program_id)];
+    logs.extend(data_logs);
+    logs.push(format!("Program {} success", program_id));
+    logs
+}
+
+#[test]
+fn test_event_discriminator() {
+    let hash = solana_program::hash::hash(b"event:Deposited");
+    assert_eq!(Deposited::DISCRIMINATOR, hash.to_bytes()[..8]);
+    assert_ne!(Deposited::DISCRIMINATOR, Withdrawn::DISCRIMINATOR);
+}
+
+#[test]
+fn test_emit_and_parse_events() {
+    common::setup_syscall_stubs();
+    let vault = Pubkey::new_unique();
+
+    emit!(Deposited {
+        vault,
+        amount: 500,
+        memo: "first".to_string(),
+    })
+    .unwrap();
+    emit!(Withdrawn { amount: 200 }).unwrap();
+    emit!(Deposited {
+        vault,
+        amount: 50,
+        memo: String::new(),
+    })
+    .unwrap();
+
+    let logs = transaction_logs(&ID, common::take_data_logs());
+
+    let deposits = parse_events::<Deposited>(&ID, &logs).unwrap();
+    assert_eq!(deposits.len(), 2);
+    assert_eq!(deposits[0].vault, vault);
+    assert_eq!(deposits[0].amount, 500);
+    assert_eq!(deposits[0].memo, "first");
+    assert_eq!(deposits[1].amount, 50);
+
+    let withdrawals = parse_events::<Withdrawn>(&ID, &logs).unwrap();
+    assert_eq!(withdrawals.len(), 1);
+    assert_eq!(withdrawals[0].amount, 200);
+}
+
+#[test]
+fn test_parse_events_skips_other_programs() {
+    common::setup_syscall_stubs();
+    let other_program = Pubkey::new_unique();
+
+    emit!(Withdrawn { amount: 1 }).unwrap();
+    let ours = common::take_data_logs();
+    emit!(Withdrawn { amount: 2 }).unwrap();
+    let theirs = common::take_data_logs();
+
+    // Our program invokes another one that logs the same event type
+    let mut logs = vec![format!("Program {} invoke [1]", ID)];
+    logs.push(format!("Program {} invoke [2]", other_program));
+    logs.extend(theirs.clone());
+    logs.push(format!("Program {} success", other_program));
+    logs.extend(ours);
+    logs.push("Program log: done".to_string());
+    logs.push(format!("Program {} success", ID));
+    // And a later top-level instruction of the other program
+    logs.extend(transaction_logs(&other_program, theirs));
+
+    let withdrawals = parse_events::<Withdrawn>(&ID, &logs).unwrap();
+    assert_eq!(withdrawals.len(), 1);
+    assert_eq!(withdrawals[0].amount, 1);
+}
+
+#[test]
+fn test_parse_events_rejects_malformed_data() {
+    let mut data = vec![0u8; Withdrawn::SPACE];
+    let written = Withdrawn { amount: 7 }.serialize(&mut data).unwrap();
+
+    // Discriminator of a `Withdrawn` with its amount cut off
+    let truncated = format!("Program data: {}", STANDARD.encode(&data[..written - 1]));
+    let logs = transaction_logs(&ID, vec![truncated]);
+    assert!(parse_events::<Withdrawn>(&ID, &logs).is_err());
+
+    let logs = transaction_logs(&ID, vec!["Program data: not-base64!".to_string()]);
+    assert_eq!(
+        parse_events::<Withdrawn>(&ID, &logs).err(),
+        Some(ProgramError::InvalidArgument)
+    );
+}
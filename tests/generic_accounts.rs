@@ -0,0 +1,309 @@
+mod common;
+
+use common::{create_account_info, run_accounts};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{
+    declare_id, Account, Accounts, AccountsExit, AnchorDeserialize, AnchorSerialize, Key, Owner,
+    Signer, ToAccountInfos,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+// Shared by every vault flavor
+pub trait Vault: AnchorSerialize + AnchorDeserialize + Owner {
+    fn authority(&self) -> Pubkey;
+    fn balance(&self) -> u64;
+    fn deposit(&mut self, amount: u64);
+}
+
+#[mini_anchor::account]
+pub struct SolVault {
+    pub authority: Pubkey,
+    pub lamports: u64,
+}
+
+impl Vault for SolVault {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+
+    fn balance(&self) -> u64 {
+        self.lamports
+    }
+
+    fn deposit(&mut self, amount: u64) {
+        self.lamports += amount;
+    }
+}
+
+#[mini_anchor::account]
+pub struct TokenVault {
+    pub mint: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+impl Vault for TokenVault {
+    fn authority(&self) -> Pubkey {
+        self.authority
+    }
+
+    fn balance(&self) -> u64 {
+        self.amount
+    }
+
+    fn deposit(&mut self, amount: u64) {
+        self.amount += amount;
+    }
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info, T: Vault> {
+    #[account(mut, constraint = vault.authority() == authority.key())]
+    pub vault: Account<'info, T>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info, T, const MIN: u64>
+where
+    T: Vault,
+{
+    #[account(mut, constraint = vault.balance() >= MIN)]
+    pub vault: Account<'info, T>,
+    pub authority: Signer<'info>,
+}
+
+// A vault at an address derived from its authority
+#[derive(Accounts)]
+pub struct VaultPda<'info, T: Vault> {
+    #[account(seeds = [b"vault", authority.key().as_ref()], bump)]
+    pub vault: Account<'info, T>,
+    pub authority: Signer<'info>,
+}
+
+// Generic over the vault type of the nested struct, whose bumps depend on it
+#[derive(Accounts)]
+pub struct Audit<'info, T: Vault> {
+    pub pda: VaultPda<'info, T>,
+    pub auditor: Signer<'info>,
+}
+
+// Generic over a whole field type, a single account or a nested struct
+#[derive(Accounts)]
+pub struct Approve<'info, A>
+where
+    A: Accounts<'info> + AccountsExit<'info> + ToAccountInfos<'info>,
+{
+    pub approver: A,
+    pub authority: Signer<'info>,
+}
+
+// Runs `Deposit<T>` against a serialized vault, deposits `amount` and exits.
+// Also loads `Withdraw<T, 10>` from the same accounts.
+fn try_deposit<T: Vault>(
+    vault: T,
+    authority: Pubkey,
+    amount: u64,
+) -> Result<(T, Result<(), ProgramError>), ProgramError> {
+    let vault_key = Pubkey::new_unique();
+    let system_program_id = solana_program::system_program::ID;
+
+    let mut vault_lamports = 1000u64;
+    let mut vault_data = vec![0u8; 128];
+    vault.serialize(&mut vault_data).unwrap();
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &vault_key,
+            false,
+            true,
+            &mut vault_lamports,
+            &mut vault_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+    ];
+
+    run_accounts(
+        &mut accounts.as_slice(),
+        &mut DepositBumps::default(),
+        |ctx: &mut Deposit<T>| ctx.vault.deposit(amount),
+    )?;
+
+    let withdraw = Withdraw::<T, 10>::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut WithdrawBumps::default(),
+    )
+    .map(|_| ());
+
+    let (vault, _) = T::deserialize(&accounts[0].data.borrow())?;
+    Ok((vault, withdraw))
+}
+
+#[test]
+fn test_generic_accounts() {
+    let authority = Pubkey::new_unique();
+
+    let vault = SolVault {
+        authority,
+        lamports: 5,
+    };
+    let (vault, withdraw) = try_deposit(vault, authority, 5).unwrap();
+    assert_eq!(vault.lamports, 10);
+    assert_eq!(withdraw, Ok(()));
+
+    let vault = TokenVault {
+        mint: Pubkey::new_unique(),
+        authority,
+        amount: 1,
+    };
+    let (vault, withdraw) = try_deposit(vault, authority, 2).unwrap();
+    assert_eq!(vault.amount, 3);
+    assert_eq!(withdraw, Err(ErrorCode::ConstraintRaw.into()));
+}
+
+#[test]
+fn test_generic_accounts_constraints() {
+    let vault = SolVault {
+        authority: Pubkey::new_unique(),
+        lamports: 5,
+    };
+    let result = try_deposit(vault, Pubkey::new_unique(), 5);
+    assert_eq!(result.err(), Some(ErrorCode::ConstraintRaw.into()));
+}
+
+#[test]
+fn test_generic_nested_accounts() {
+    let authority = Pubkey::new_unique();
+    let auditor = Pubkey::new_unique();
+    let (vault_key, bump) = Pubkey::find_program_address(&[b"vault", authority.as_ref()], &ID);
+    let system_program_id = solana_program::system_program::ID;
+
+    let mut vault_lamports = 1000u64;
+    let mut vault_data = vec![0u8; 128];
+    SolVault {
+        authority,
+        lamports: 7,
+    }
+    .serialize(&mut vault_data)
+    .unwrap();
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut auditor_lamports = 1000u64;
+    let mut auditor_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &vault_key,
+            false,
+            false,
+            &mut vault_lamports,
+            &mut vault_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &auditor,
+            true,
+            false,
+            &mut auditor_lamports,
+            &mut auditor_data,
+            &system_program_id,
+        ),
+    ];
+
+    let mut bumps = AuditBumps::<SolVault>::default();
+    let ctx =
+        Audit::<SolVault>::try_accounts(&ID, &mut accounts.as_slice(), &[], &mut bumps).unwrap();
+
+    assert_eq!(ctx.pda.vault.balance(), 7);
+    assert_eq!(bumps.pda.vault, bump);
+}
+
+#[test]
+fn test_generic_field_type() {
+    let authority = Pubkey::new_unique();
+    let approver = Pubkey::new_unique();
+    let (vault_key, bump) = Pubkey::find_program_address(&[b"vault", authority.as_ref()], &ID);
+    let system_program_id = solana_program::system_program::ID;
+
+    let mut vault_lamports = 1000u64;
+    let mut vault_data = vec![0u8; 128];
+    SolVault {
+        authority,
+        lamports: 7,
+    }
+    .serialize(&mut vault_data)
+    .unwrap();
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut approver_lamports = 1000u64;
+    let mut approver_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &vault_key,
+            false,
+            false,
+            &mut vault_lamports,
+            &mut vault_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &approver,
+            true,
+            false,
+            &mut approver_lamports,
+            &mut approver_data,
+            &system_program_id,
+        ),
+    ];
+
+    // A nested struct records its bumps under the field
+    let mut bumps = ApproveBumps::<VaultPda<SolVault>>::default();
+    let ctx =
+        Approve::<VaultPda<SolVault>>::try_accounts(&ID, &mut accounts.as_slice(), &[], &mut bumps)
+            .unwrap();
+    ctx.exit(&ID).unwrap();
+    assert_eq!(ctx.approver.vault.balance(), 7);
+    assert_eq!(bumps.approver.vault, bump);
+
+    // A single account has none
+    let ctx = Approve::<Signer>::try_accounts(
+        &ID,
+        &mut &accounts[1..],
+        &[],
+        &mut ApproveBumps::default(),
+    )
+    .unwrap();
+    ctx.exit(&ID).unwrap();
+    assert_eq!(ctx.approver.key(), authority);
+}
@@ -0,0 +1,254 @@
+mod common;
+
+use common::create_account_info;
+use mini_anchor::error::ErrorCode;
+use mini_anchor::system_program::{self, System};
+use mini_anchor::{declare_id, Account, Accounts, AccountsExit, Program, Signer};
+use solana_program::pubkey::Pubkey;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = payer, space = 8 + 32 + 8 + 16)]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeDefaultSpace<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(init, payer = payer)]
+    pub counter: Account<'info, Counter>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeChecked<'info> {
+    // Refers to `counter`, which is declared and created further down
+    #[account(mut, constraint = counter.count == 0)]
+    pub payer: Signer<'info>,
+    #[account(init, payer = payer)]
+    pub counter: Account<'info, Counter>,
+    pub system_program: Program<'info, System>,
+}
+
+#[test]
+fn test_init_creates_account() {
+    common::setup_syscall_stubs();
+
+    let space = 8 + 32 + 8 + 16;
+    let counter_key = Pubkey::new_unique();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut counter_lamports = 0u64;
+    let mut counter_data = vec![0u8; space];
+    let counter_owner = system_program::ID;
+    let mut payer_lamports = 1_000_000_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            true,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &counter_owner,
+        ),
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let mut ctx = Initialize::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    )
+    .unwrap();
+
+    // Funded to rent exemption by the payer and handed to the program
+    let rent = common::minimum_balance(space);
+    assert_eq!(accounts[0].lamports(), rent);
+    assert_eq!(accounts[1].lamports(), 1_000_000_000 - rent);
+    assert_eq!(accounts[0].owner, &ID);
+    assert_eq!(accounts[0].data.borrow()[..8], Counter::DISCRIMINATOR);
+    assert_eq!(ctx.counter.count, 0);
+
+    // Values set by the handler are persisted on exit
+    ctx.counter.authority = payer_key;
+    ctx.counter.count = 1;
+    ctx.exit(&ID).unwrap();
+
+    let counter = Counter::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(counter.authority, payer_key);
+    assert_eq!(counter.count, 1);
+}
+
+#[test]
+fn test_init_tops_up_prefunded_account() {
+    common::setup_syscall_stubs();
+
+    let space = Counter::SPACE;
+    let counter_key = Pubkey::new_unique();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    // Someone already sent lamports to the address
+    let mut counter_lamports = 100u64;
+    let mut counter_data = vec![0u8; space];
+    let counter_owner = system_program::ID;
+    let mut payer_lamports = 1_000_000_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &counter_key,
+            true,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &counter_owner,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let ctx = InitializeDefaultSpace::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeDefaultSpaceBumps::default(),
+    )
+    .unwrap();
+
+    let rent = common::minimum_balance(space);
+    assert_eq!(accounts[1].lamports(), rent);
+    assert_eq!(accounts[0].lamports(), 1_000_000_000 - (rent - 100));
+    assert_eq!(accounts[1].owner, &ID);
+    assert_eq!(ctx.counter.count, 0);
+}
+
+#[test]
+fn test_init_requires_writable_account() {
+    common::setup_syscall_stubs();
+
+    let counter_key = Pubkey::new_unique();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut counter_lamports = 0u64;
+    let mut counter_data = vec![0u8; Counter::SPACE];
+    let counter_owner = system_program::ID;
+    let mut payer_lamports = 1_000_000_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &counter_key,
+            true,
+            false, // not writable
+            &mut counter_lamports,
+            &mut counter_data,
+            &counter_owner,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let result = InitializeDefaultSpace::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeDefaultSpaceBumps::default(),
+    );
+    assert_eq!(result.err(), Some(ErrorCode::ConstraintMut.into()));
+    assert_eq!(accounts[0].lamports(), 1_000_000_000);
+}
+
+#[test]
+fn test_constraint_refers_to_later_init_account() {
+    common::setup_syscall_stubs();
+
+    let counter_key = Pubkey::new_unique();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut counter_lamports = 0u64;
+    let mut counter_data = vec![0u8; Counter::SPACE];
+    let counter_owner = system_program::ID;
+    let mut payer_lamports = 1_000_000_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &counter_key,
+            true,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &counter_owner,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let ctx = InitializeChecked::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeCheckedBumps::default(),
+    )
+    .unwrap();
+
+    // Created before the payer's constraint read it
+    assert_eq!(accounts[1].owner, &ID);
+    assert_eq!(ctx.counter.count, 0);
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "init-if-needed")]
+
+mod common;
+
+use common::{account_data, create_account_info};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::system_program::{self, System};
+use mini_anchor::{declare_id, Account, Accounts, Program, Signer};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init_if_needed, payer = payer)]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Runs try_accounts and returns the loaded count and the counter's owner
+fn try_initialize(
+    counter_data: &mut [u8],
+    counter_lamports: u64,
+    counter_owner: Pubkey,
+) -> Result<(u64, Pubkey), ProgramError> {
+    common::setup_syscall_stubs();
+
+    let counter_key = Pubkey::new_unique();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut counter_lamports = counter_lamports;
+    let mut payer_lamports = 1_000_000_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            true,
+            true,
+            &mut counter_lamports,
+            counter_data,
+            &counter_owner,
+        ),
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let ctx = Initialize::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    )?;
+    Ok((ctx.counter.count, *accounts[0].owner))
+}
+
+#[test]
+fn test_creates_missing_account() {
+    let mut data = vec![0u8; Counter::SPACE];
+
+    let (count, owner) = try_initialize(&mut data, 0, system_program::ID).unwrap();
+    assert_eq!(count, 0);
+    assert_eq!(owner, ID);
+    assert_eq!(data[..8], Counter::DISCRIMINATOR);
+}
+
+#[test]
+fn test_loads_existing_account() {
+    let mut data = account_data(Counter {
+        authority: Pubkey::new_unique(),
+        count: 5,
+    });
+    let rent = common::minimum_balance(Counter::SPACE);
+
+    // Not reset to a fresh account
+    let (count, owner) = try_initialize(&mut data, rent, ID).unwrap();
+    assert_eq!(count, 5);
+    assert_eq!(owner, ID);
+}
+
+#[test]
+fn test_rejects_uninitialized_program_account() {
+    // Owned by the program but never given a discriminator
+    let mut data = vec![0u8; Counter::SPACE];
+    let rent = common::minimum_balance(Counter::SPACE);
+
+    let result = try_initialize(&mut data, rent, ID);
+    assert_eq!(
+        result.err(),
+        Some(ErrorCode::AccountDiscriminatorMismatch.into())
+    );
+}
+
+#[test]
+fn test_rejects_wrong_size() {
+    let mut data = vec![0u8; Counter::SPACE + 8];
+    Counter {
+        authority: Pubkey::new_unique(),
+        count: 5,
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    let rent = common::minimum_balance(Counter::SPACE + 8);
+
+    let result = try_initialize(&mut data, rent, ID);
+    assert_eq!(result.err(), Some(ErrorCode::ConstraintSpace.into()));
+}
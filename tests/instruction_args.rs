@@ -0,0 +1,136 @@
+mod common;
+
+use common::create_account_info;
+use mini_anchor::error::ErrorCode;
+use mini_anchor::prelude::*;
+use mini_anchor::system_program::{self, System};
+use mini_anchor::{Account, Program, Signer};
+use solana_program::{entrypoint::ProgramResult, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[account]
+pub struct Profile {
+    pub owner: Pubkey,
+    pub extra: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String, extra: u64)]
+pub struct CreateProfile<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = Profile::SPACE + extra as usize,
+        seeds = [b"profile", name.as_bytes()],
+        bump,
+        constraint = extra <= 64,
+    )]
+    pub profile: Account<'info, Profile>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[program]
+pub mod profile_program {
+    use super::*;
+
+    pub fn create_profile(
+        ctx: Context<CreateProfile>,
+        _name: String,
+        extra: u64,
+        flag: bool,
+    ) -> ProgramResult {
+        ctx.accounts.profile.owner = *ctx.accounts.payer.info.key;
+        ctx.accounts.profile.extra = if flag { extra } else { 0 };
+        Ok(())
+    }
+}
+
+fn instruction_data(name: &str, extra: u64) -> Vec<u8> {
+    let mut data = vec![0u8; 64];
+    let len = instruction::CreateProfile {
+        _name: name.to_string(),
+        extra,
+        flag: true,
+    }
+    .serialize(&mut data)
+    .unwrap();
+    data.truncate(len);
+    data
+}
+
+// Runs the program with a profile account at the PDA for `seed_name`,
+// sized for `extra` trailing bytes
+fn try_create_profile(seed_name: &str, data: &[u8], extra: u64) -> ProgramResult {
+    common::setup_syscall_stubs();
+
+    let (profile_key, _) = Pubkey::find_program_address(&[b"profile", seed_name.as_bytes()], &ID);
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut profile_lamports = 0u64;
+    let mut profile_data = vec![0u8; Profile::SPACE + extra as usize];
+    let profile_owner = system_program::ID;
+    let mut payer_lamports = 1_000_000_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &profile_key,
+            false,
+            true,
+            &mut profile_lamports,
+            &mut profile_data,
+            &profile_owner,
+        ),
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    entry(&ID, &accounts, data)?;
+
+    assert_eq!(accounts[0].owner, &ID);
+    let profile = Profile::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(profile.owner, payer_key);
+    assert_eq!(profile.extra, extra);
+    Ok(())
+}
+
+#[test]
+fn test_instruction_args_in_seeds_and_space() {
+    // Space must match the pre-sized buffer exactly for the stubbed CreateAccount
+    try_create_profile("alice", &instruction_data("alice", 16), 16).unwrap();
+}
+
+#[test]
+fn test_instruction_arg_seeds_mismatch() {
+    let result = try_create_profile("alice", &instruction_data("bob", 16), 16);
+    assert_eq!(result, Err(ErrorCode::ConstraintSeeds.into()));
+}
+
+#[test]
+fn test_instruction_arg_in_constraint() {
+    let result = try_create_profile("alice", &instruction_data("alice", 65), 65);
+    assert_eq!(result, Err(ErrorCode::ConstraintRaw.into()));
+}
+
+#[test]
+fn test_missing_instruction_args() {
+    let result =
+        CreateProfile::try_accounts(&ID, &mut &[][..], &[], &mut CreateProfileBumps::default());
+    assert_eq!(
+        result.err(),
+        Some(ErrorCode::InstructionDidNotDeserialize.into())
+    );
+}
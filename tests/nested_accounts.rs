@@ -0,0 +1,135 @@
+mod common;
+
+use common::{account_data, create_account_info, run_accounts};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{declare_id, Account, Accounts, Signer};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Config {
+    pub authority: Pubkey,
+    pub max: u64,
+}
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub count: u64,
+}
+
+// Shared by every instruction restricted to the config authority
+#[derive(Accounts)]
+pub struct Admin<'info> {
+    pub authority: Signer<'info>,
+    #[account(has_one = authority, seeds = [b"config"], bump)]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct Increment<'info> {
+    pub admin: Admin<'info>,
+    #[account(mut, constraint = counter.count < admin.config.max)]
+    pub counter: Account<'info, Counter>,
+}
+
+// Runs try_accounts with one extra account after the declared ones, increments and exits.
+// Returns the config bump and how many accounts were left over.
+fn try_increment(
+    signer: Pubkey,
+    config_authority: Pubkey,
+    counter_data: &mut [u8],
+) -> Result<(u8, usize), ProgramError> {
+    let (config_key, _) = Pubkey::find_program_address(&[b"config"], &ID);
+    let counter_key = Pubkey::new_unique();
+    let extra_key = Pubkey::new_unique();
+    let system_program_id = solana_program::system_program::ID;
+
+    let mut config_data = vec![0u8; Config::SPACE];
+    Config {
+        authority: config_authority,
+        max: 3,
+    }
+    .try_serialize(&mut config_data)
+    .unwrap();
+
+    let mut signer_lamports = 1000u64;
+    let mut signer_data = vec![0u8; 0];
+    let mut config_lamports = 1000u64;
+    let mut counter_lamports = 1000u64;
+    let mut extra_lamports = 1000u64;
+    let mut extra_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &signer,
+            true,
+            false,
+            &mut signer_lamports,
+            &mut signer_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &config_key,
+            false,
+            false,
+            &mut config_lamports,
+            &mut config_data,
+            &ID,
+        ),
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &extra_key,
+            false,
+            false,
+            &mut extra_lamports,
+            &mut extra_data,
+            &system_program_id,
+        ),
+    ];
+
+    let mut remaining = accounts.as_slice();
+    let mut bumps = IncrementBumps::default();
+    run_accounts(&mut remaining, &mut bumps, |ctx: &mut Increment| {
+        ctx.counter.count += 1
+    })?;
+
+    Ok((bumps.admin.config, remaining.len()))
+}
+
+#[test]
+fn test_nested_accounts() {
+    let authority = Pubkey::new_unique();
+    let mut counter = account_data(Counter { count: 1 });
+
+    let (bump, remaining) = try_increment(authority, authority, &mut counter).unwrap();
+
+    let (_, expected_bump) = Pubkey::find_program_address(&[b"config"], &ID);
+    assert_eq!(bump, expected_bump);
+    assert_eq!(remaining, 1);
+    assert_eq!(Counter::try_deserialize(&counter).unwrap().count, 2);
+}
+
+#[test]
+fn test_nested_constraints() {
+    let mut counter = account_data(Counter { count: 1 });
+
+    let result = try_increment(Pubkey::new_unique(), Pubkey::new_unique(), &mut counter);
+    assert_eq!(result, Err(ErrorCode::ConstraintHasOne.into()));
+}
+
+#[test]
+fn test_constraint_on_nested_field() {
+    let authority = Pubkey::new_unique();
+    let mut counter = account_data(Counter { count: 3 });
+
+    let result = try_increment(authority, authority, &mut counter);
+    assert_eq!(result, Err(ErrorCode::ConstraintRaw.into()));
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use common::{account_data, create_account_info, run_accounts};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{declare_id, Account, Accounts, Signer};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut, has_one = authority)]
+    pub counter: Box<Account<'info, Counter>>,
+    pub authority: Signer<'info>,
+    #[account(mut, constraint = bonus.count < 10)]
+    pub bonus: Option<Box<Account<'info, Counter>>>,
+    pub referrer: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(zero)]
+    pub counter: Box<Account<'info, Counter>>,
+}
+
+// Runs try_accounts, bumps every counter that was passed and exits.
+// `None` puts the program id in that optional slot.
+fn try_update(
+    authority: Pubkey,
+    counter_data: &mut [u8],
+    bonus_data: Option<&mut [u8]>,
+    with_referrer: bool,
+) -> Result<(bool, bool), ProgramError> {
+    let counter_key = Pubkey::new_unique();
+    let bonus_key = bonus_data.as_ref().map_or(ID, |_| Pubkey::new_unique());
+    let referrer_key = if with_referrer {
+        Pubkey::new_unique()
+    } else {
+        ID
+    };
+    let system_program_id = solana_program::system_program::ID;
+
+    let mut counter_lamports = 1000u64;
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut bonus_lamports = 1000u64;
+    let mut empty_data = vec![0u8; 0];
+    let mut referrer_lamports = 1000u64;
+    let mut referrer_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &bonus_key,
+            false,
+            true,
+            &mut bonus_lamports,
+            bonus_data.unwrap_or(&mut empty_data),
+            &ID,
+        ),
+        create_account_info(
+            &referrer_key,
+            true,
+            false,
+            &mut referrer_lamports,
+            &mut referrer_data,
+            &system_program_id,
+        ),
+    ];
+
+    let ctx = run_accounts(
+        &mut accounts.as_slice(),
+        &mut UpdateBumps::default(),
+        |ctx: &mut Update| {
+            ctx.counter.count += 1;
+            if let Some(bonus) = ctx.bonus.as_mut() {
+                bonus.count += 1;
+            }
+        },
+    )?;
+
+    Ok((ctx.bonus.is_some(), ctx.referrer.is_some()))
+}
+
+#[test]
+fn test_optional_accounts_present() {
+    let authority = Pubkey::new_unique();
+    let mut counter = account_data(Counter {
+        authority,
+        count: 1,
+    });
+    let mut bonus = account_data(Counter {
+        authority,
+        count: 5,
+    });
+
+    let present = try_update(authority, &mut counter, Some(&mut bonus), true).unwrap();
+    assert_eq!(present, (true, true));
+    assert_eq!(Counter::try_deserialize(&counter).unwrap().count, 2);
+    assert_eq!(Counter::try_deserialize(&bonus).unwrap().count, 6);
+}
+
+#[test]
+fn test_optional_accounts_absent() {
+    let authority = Pubkey::new_unique();
+    let mut counter = account_data(Counter {
+        authority,
+        count: 1,
+    });
+
+    let present = try_update(authority, &mut counter, None, false).unwrap();
+    assert_eq!(present, (false, false));
+    assert_eq!(Counter::try_deserialize(&counter).unwrap().count, 2);
+}
+
+#[test]
+fn test_optional_account_constraints() {
+    let authority = Pubkey::new_unique();
+    let mut counter = account_data(Counter {
+        authority,
+        count: 1,
+    });
+    let mut bonus = account_data(Counter {
+        authority,
+        count: 10,
+    });
+
+    let result = try_update(authority, &mut counter, Some(&mut bonus), false);
+    assert_eq!(result, Err(ErrorCode::ConstraintRaw.into()));
+}
+
+#[test]
+fn test_boxed_account_constraints() {
+    let mut counter = account_data(Counter {
+        authority: Pubkey::new_unique(),
+        count: 1,
+    });
+
+    let result = try_update(Pubkey::new_unique(), &mut counter, None, false);
+    assert_eq!(result, Err(ErrorCode::ConstraintHasOne.into()));
+}
+
+// Runs try_accounts on a zeroed, boxed counter, sets its count and exits
+fn try_initialize(data: &mut [u8]) -> Result<(), ProgramError> {
+    common::setup_syscall_stubs();
+
+    let key = Pubkey::new_unique();
+    let mut lamports = common::minimum_balance(Counter::SPACE);
+    let accounts = vec![create_account_info(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        data,
+        &ID,
+    )];
+
+    run_accounts(
+        &mut accounts.as_slice(),
+        &mut InitializeBumps::default(),
+        |ctx: &mut Initialize| ctx.counter.count = 3,
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn test_boxed_zero_account() {
+    let mut data = vec![0u8; Counter::SPACE];
+
+    try_initialize(&mut data).unwrap();
+    assert_eq!(data[..8], Counter::DISCRIMINATOR);
+    assert_eq!(Counter::try_deserialize(&data).unwrap().count, 3);
+}
@@ -0,0 +1,311 @@
+mod common;
+
+use common::{account_data, create_account_info};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::system_program::{self, System};
+use mini_anchor::{declare_id, Account, Accounts, Key, Program, Signer};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = authority, seeds = [b"counter", authority.key().as_ref()], bump)]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// Seeds can reference fields declared after the PDA
+#[derive(Accounts)]
+pub struct Increment<'info> {
+    #[account(mut, seeds = [b"counter", authority.key().as_ref()], bump = counter.bump)]
+    pub counter: Account<'info, Counter>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Read<'info> {
+    pub authority: Signer<'info>,
+    #[account(seeds = [b"counter", authority.key().as_ref()], bump)]
+    pub counter: Account<'info, Counter>,
+}
+
+fn counter_address(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"counter", authority.as_ref()], &ID)
+}
+
+#[test]
+fn test_init_pda_signs_with_seeds() {
+    common::setup_syscall_stubs();
+
+    let authority_key = Pubkey::new_unique();
+    let (counter_key, expected_bump) = counter_address(&authority_key);
+    let system_program_id = system_program::ID;
+
+    let mut counter_lamports = 0u64;
+    let mut counter_data = vec![0u8; Counter::SPACE];
+    let mut authority_lamports = 1_000_000_000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    // The PDA cannot sign the transaction itself
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            true,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let mut bumps = InitializeBumps::default();
+    Initialize::try_accounts(&ID, &mut accounts.as_slice(), &[], &mut bumps).unwrap();
+
+    assert_eq!(bumps.counter, expected_bump);
+    assert_eq!(accounts[0].owner, &ID);
+    assert_eq!(
+        accounts[0].lamports(),
+        common::minimum_balance(Counter::SPACE)
+    );
+}
+
+#[test]
+fn test_init_pda_rejects_wrong_address() {
+    common::setup_syscall_stubs();
+
+    let authority_key = Pubkey::new_unique();
+    let counter_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut counter_lamports = 0u64;
+    let mut counter_data = vec![0u8; Counter::SPACE];
+    let mut authority_lamports = 1_000_000_000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            true,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &system_program_id,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            true,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let result = Initialize::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InitializeBumps::default(),
+    );
+    assert_eq!(
+        result.err(),
+        Some(ProgramError::from(ErrorCode::ConstraintSeeds))
+    );
+
+    // Nothing was created
+    assert_eq!(accounts[0].lamports(), 0);
+    assert_eq!(accounts[0].owner, &system_program_id);
+}
+
+#[test]
+fn test_canonical_bump_is_recorded() {
+    let authority_key = Pubkey::new_unique();
+    let (counter_key, expected_bump) = counter_address(&authority_key);
+
+    let mut authority_lamports = 0u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut counter_lamports = 0u64;
+    let mut counter_data = account_data(Counter {
+        authority: authority_key,
+        count: 0,
+        bump: expected_bump,
+    });
+
+    let accounts = vec![
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program::ID,
+        ),
+        create_account_info(
+            &counter_key,
+            false,
+            false,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+    ];
+
+    let mut bumps = ReadBumps::default();
+    Read::try_accounts(&ID, &mut accounts.as_slice(), &[], &mut bumps).unwrap();
+    assert_eq!(bumps.counter, expected_bump);
+}
+
+#[test]
+fn test_seeds_reject_other_authority() {
+    let authority_key = Pubkey::new_unique();
+    let other_authority = Pubkey::new_unique();
+    let (counter_key, bump) = counter_address(&other_authority);
+
+    let mut authority_lamports = 0u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut counter_lamports = 0u64;
+    let mut counter_data = account_data(Counter {
+        authority: other_authority,
+        count: 0,
+        bump,
+    });
+
+    let accounts = vec![
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program::ID,
+        ),
+        create_account_info(
+            &counter_key,
+            false,
+            false,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+    ];
+
+    let result = Read::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut ReadBumps::default(),
+    );
+    assert_eq!(
+        result.err(),
+        Some(ProgramError::from(ErrorCode::ConstraintSeeds))
+    );
+}
+
+#[test]
+fn test_stored_bump() {
+    let authority_key = Pubkey::new_unique();
+    let (counter_key, bump) = counter_address(&authority_key);
+
+    let mut counter_lamports = 0u64;
+    let mut counter_data = account_data(Counter {
+        authority: authority_key,
+        count: 3,
+        bump,
+    });
+    let mut authority_lamports = 0u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program::ID,
+        ),
+    ];
+
+    let mut bumps = IncrementBumps::default();
+    let ctx = Increment::try_accounts(&ID, &mut accounts.as_slice(), &[], &mut bumps).unwrap();
+    assert_eq!(bumps.counter, bump);
+    assert_eq!(ctx.counter.count, 3);
+}
+
+#[test]
+fn test_stored_bump_mismatch() {
+    let authority_key = Pubkey::new_unique();
+    let (counter_key, bump) = counter_address(&authority_key);
+
+    // A non-canonical bump either fails to derive or derives another address
+    let mut counter_lamports = 0u64;
+    let mut counter_data = account_data(Counter {
+        authority: authority_key,
+        count: 0,
+        bump: bump.wrapping_sub(1),
+    });
+    let mut authority_lamports = 0u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &system_program::ID,
+        ),
+    ];
+
+    let result = Increment::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut IncrementBumps::default(),
+    );
+    assert_eq!(
+        result.err(),
+        Some(ProgramError::from(ErrorCode::ConstraintSeeds))
+    );
+}
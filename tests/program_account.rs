@@ -0,0 +1,86 @@
+use mini_anchor::error::ErrorCode;
+use mini_anchor::system_program::{self, System};
+use mini_anchor::{declare_id, Accounts, Id, Program};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[derive(Accounts)]
+pub struct Invoke<'info> {
+    pub system_program: Program<'info, System>,
+    pub this_program: Program<'info, ThisProgram>,
+}
+
+fn create_program_info<'a>(
+    key: &'a Pubkey,
+    executable: bool,
+    lamports: &'a mut u64,
+    data: &'a mut [u8],
+    owner: &'a Pubkey,
+) -> AccountInfo<'a> {
+    AccountInfo::new(key, false, false, lamports, data, owner, executable, 0)
+}
+
+// Runs try_accounts with the given system program and own program accounts
+fn try_invoke(
+    system_key: Pubkey,
+    system_executable: bool,
+    program_key: Pubkey,
+) -> Result<(), ProgramError> {
+    let loader = Pubkey::new_unique();
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+    let mut program_lamports = 1u64;
+    let mut program_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_program_info(
+            &system_key,
+            system_executable,
+            &mut system_lamports,
+            &mut system_data,
+            &loader,
+        ),
+        create_program_info(
+            &program_key,
+            true,
+            &mut program_lamports,
+            &mut program_data,
+            &loader,
+        ),
+    ];
+
+    Invoke::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut InvokeBumps::default(),
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn test_ids() {
+    assert_eq!(System::id(), system_program::ID);
+    assert_eq!(ThisProgram::id(), ID);
+}
+
+#[test]
+fn test_program_accounts() {
+    assert!(try_invoke(system_program::ID, true, ID).is_ok());
+}
+
+#[test]
+fn test_wrong_program_id() {
+    let result = try_invoke(Pubkey::new_unique(), true, ID);
+    assert_eq!(result, Err(ErrorCode::InvalidProgramId.into()));
+
+    let result = try_invoke(system_program::ID, true, Pubkey::new_unique());
+    assert_eq!(result, Err(ErrorCode::InvalidProgramId.into()));
+}
+
+#[test]
+fn test_program_not_executable() {
+    let result = try_invoke(system_program::ID, false, ID);
+    assert_eq!(result, Err(ErrorCode::InvalidProgramExecutable.into()));
+}
@@ -0,0 +1,280 @@
+mod common;
+
+use common::{account_data, create_account_info};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::prelude::*;
+use mini_anchor::{Account, Signer};
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Increment<'info> {
+    #[account(mut)]
+    pub counter: Account<'info, Counter>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReadCounter<'info> {
+    pub counter: Account<'info, Counter>,
+}
+
+#[program]
+pub mod counter_program {
+    use super::*;
+
+    pub fn increment(ctx: Context<Increment>, amount: u64) -> ProgramResult {
+        if amount == 0 {
+            // Fails after mutating, nothing must be written back
+            ctx.accounts.counter.count = u64::MAX;
+            return Err(ProgramError::InvalidArgument);
+        }
+        ctx.accounts.counter.count += amount;
+        Ok(())
+    }
+
+    pub fn reset(ctx: Context<Increment>) -> ProgramResult {
+        ctx.accounts.counter.count = 0;
+        Ok(())
+    }
+
+    pub fn count_remaining(ctx: Context<Increment>) -> ProgramResult {
+        if ctx.program_id != &ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        ctx.accounts.counter.count = ctx.remaining_accounts.len() as u64;
+        Ok(())
+    }
+
+    pub fn read(ctx: Context<ReadCounter>) -> ProgramResult {
+        let _ = ctx.accounts.counter.count;
+        Ok(())
+    }
+
+    pub fn tamper(ctx: Context<ReadCounter>) -> ProgramResult {
+        ctx.accounts.counter.count += 1;
+        Ok(())
+    }
+}
+
+fn instruction_data<T: AnchorSerialize>(ix: &T) -> Vec<u8> {
+    let mut buf = vec![0u8; 256];
+    let written = ix.serialize(&mut buf).unwrap();
+    buf.truncate(written);
+    buf
+}
+
+#[test]
+fn test_instruction_discriminator() {
+    let hash = solana_program::hash::hash(b"global:increment");
+    assert_eq!(instruction::Increment::DISCRIMINATOR, hash.to_bytes()[..8]);
+}
+
+#[test]
+fn test_instruction_roundtrip() {
+    let data = instruction_data(&instruction::Increment { amount: 39 });
+    assert_eq!(data.len(), 8 + 8);
+
+    let (decoded, read) = instruction::Increment::deserialize(&data).unwrap();
+    assert_eq!(read, data.len());
+    assert_eq!(decoded.amount, 39);
+}
+
+#[test]
+fn test_entry_dispatches_to_handler() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+
+    let counter = Counter {
+        authority: authority_key,
+        count: 1,
+    };
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(counter);
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &ID,
+        ),
+    ];
+
+    let data = instruction_data(&instruction::Increment { amount: 41 });
+    entry(&ID, &accounts, &data).unwrap();
+    let counter = Counter::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(counter.count, 42);
+
+    let data = instruction_data(&instruction::Reset {});
+    entry(&ID, &accounts, &data).unwrap();
+    let counter = Counter::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(counter.count, 0);
+}
+
+#[test]
+fn test_entry_skips_write_back_on_handler_error() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+
+    let counter = Counter {
+        authority: authority_key,
+        count: 7,
+    };
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(counter);
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &ID,
+        ),
+    ];
+
+    let data = instruction_data(&instruction::Increment { amount: 0 });
+    assert_eq!(
+        entry(&ID, &accounts, &data),
+        Err(ProgramError::InvalidArgument)
+    );
+    let counter = Counter::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(counter.count, 7);
+}
+
+#[test]
+fn test_entry_rejects_modified_read_only_account() {
+    let counter_key = Pubkey::new_unique();
+
+    let counter = Counter {
+        authority: Pubkey::new_unique(),
+        count: 7,
+    };
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(counter);
+
+    let accounts = vec![create_account_info(
+        &counter_key,
+        false,
+        true,
+        &mut counter_lamports,
+        &mut counter_data,
+        &ID,
+    )];
+
+    let data = instruction_data(&instruction::Read {});
+    entry(&ID, &accounts, &data).unwrap();
+
+    let data = instruction_data(&instruction::Tamper {});
+    assert_eq!(
+        entry(&ID, &accounts, &data),
+        Err(ErrorCode::AccountNotMutable.into())
+    );
+    let counter = Counter::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(counter.count, 7);
+}
+
+#[test]
+fn test_entry_passes_remaining_accounts() {
+    let counter_key = Pubkey::new_unique();
+    let authority_key = Pubkey::new_unique();
+    let extra_key = Pubkey::new_unique();
+
+    let counter = Counter {
+        authority: authority_key,
+        count: 0,
+    };
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(counter);
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+    let mut extra_lamports = 1000u64;
+    let mut extra_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &ID,
+        ),
+        create_account_info(
+            &extra_key,
+            false,
+            false,
+            &mut extra_lamports,
+            &mut extra_data,
+            &ID,
+        ),
+    ];
+
+    let data = instruction_data(&instruction::CountRemaining {});
+    entry(&ID, &accounts, &data).unwrap();
+    let counter = Counter::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(counter.count, 1);
+}
+
+#[test]
+fn test_entry_bad_instruction_args() {
+    let accounts = vec![];
+    // Discriminator is fine but the u64 argument is truncated
+    let mut data = instruction::Increment::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&[1, 2]);
+    let result = entry(&ID, &accounts, &data);
+    assert_eq!(result, Err(ErrorCode::InstructionDidNotDeserialize.into()));
+}
+
+#[test]
+fn test_entry_unknown_instruction() {
+    let accounts = vec![];
+    let result = entry(&ID, &accounts, &[0u8; 8]);
+    assert_eq!(result, Err(ErrorCode::InstructionFallbackNotFound.into()));
+}
+
+#[test]
+fn test_entry_data_too_short() {
+    let accounts = vec![];
+    let result = entry(&ID, &accounts, &[1, 2, 3]);
+    assert_eq!(result, Err(ErrorCode::InstructionMissing.into()));
+}
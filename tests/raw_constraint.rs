@@ -0,0 +1,122 @@
+mod common;
+
+use common::{account_data, create_account_info};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{declare_id, error_code, Account, Accounts, Key, Signer};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+#[error_code]
+pub enum CounterError {
+    #[msg("Counter reached its limit")]
+    LimitReached,
+    #[msg("Only the counter authority may do this")]
+    Unauthorized,
+}
+
+#[derive(Accounts)]
+pub struct Increment<'info> {
+    #[account(
+        mut,
+        has_one = authority @ CounterError::Unauthorized,
+        constraint = counter.count < 10 @ CounterError::LimitReached
+    )]
+    pub counter: Account<'info, Counter>,
+    // Refers back to a field declared earlier
+    #[account(constraint = authority.key() != counter.key())]
+    pub authority: Signer<'info>,
+}
+
+// Runs try_accounts for a counter at `counter_key` signed by `authority_key`
+fn try_increment(
+    counter_key: Pubkey,
+    authority_key: Pubkey,
+    counter_authority: Pubkey,
+    count: u64,
+) -> Result<(), ProgramError> {
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(Counter {
+        authority: counter_authority,
+        count,
+    });
+    let mut authority_lamports = 1000u64;
+    let mut authority_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            false,
+            true,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &authority_key,
+            true,
+            false,
+            &mut authority_lamports,
+            &mut authority_data,
+            &ID,
+        ),
+    ];
+
+    Increment::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut IncrementBumps::default(),
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn test_constraints_pass() {
+    let authority = Pubkey::new_unique();
+    assert!(try_increment(Pubkey::new_unique(), authority, authority, 9).is_ok());
+}
+
+#[test]
+fn test_raw_constraint_custom_error() {
+    let authority = Pubkey::new_unique();
+    let result = try_increment(Pubkey::new_unique(), authority, authority, 10);
+    assert_eq!(result, Err(CounterError::LimitReached.into()));
+}
+
+#[test]
+fn test_raw_constraint_default_error() {
+    // Same key passed as both the counter and its authority
+    let key = Pubkey::new_unique();
+    let result = try_increment(key, key, key, 0);
+    assert_eq!(result, Err(ErrorCode::ConstraintRaw.into()));
+}
+
+#[test]
+fn test_has_one_custom_error() {
+    let result = try_increment(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        0,
+    );
+    assert_eq!(result, Err(CounterError::Unauthorized.into()));
+}
+
+#[test]
+fn test_constraints_checked_in_declaration_order() {
+    // Both the has_one and the count constraint fail, has_one comes first
+    let result = try_increment(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        10,
+    );
+    assert_eq!(result, Err(CounterError::Unauthorized.into()));
+}
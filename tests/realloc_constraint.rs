@@ -0,0 +1,262 @@
+mod common;
+
+use common::{create_account_info, SerializedAccount};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::system_program::{self, System};
+use mini_anchor::{declare_id, Account, Accounts, AccountsExit, Program, Signer};
+use solana_program::{entrypoint::MAX_PERMITTED_DATA_INCREASE, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Log {
+    pub authority: Pubkey,
+    #[max_len(1000)]
+    pub entries: Vec<u64>,
+}
+
+// Discriminator, authority, vec length prefix and the entries
+fn log_space(entries: usize) -> usize {
+    8 + 32 + 4 + 8 * entries
+}
+
+#[derive(Accounts)]
+pub struct Append<'info> {
+    #[account(
+        mut,
+        realloc = log_space(log.entries.len() + 1),
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub log: Account<'info, Log>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Pop<'info> {
+    #[account(mut, realloc = log_space(log.entries.len() - 1), realloc::payer = payer)]
+    pub log: Account<'info, Log>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Grow<'info> {
+    #[account(
+        mut,
+        realloc = log.info.data_len() + MAX_PERMITTED_DATA_INCREASE + 1,
+        realloc::payer = payer
+    )]
+    pub log: Account<'info, Log>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Rewrite<'info> {
+    #[account(mut, realloc = log_space(log.entries.len()), realloc::payer = payer)]
+    pub log: Account<'info, Log>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+fn log_account(entries: Vec<u64>) -> SerializedAccount {
+    let mut data = vec![0u8; log_space(entries.len())];
+    Log {
+        authority: Pubkey::new_unique(),
+        entries,
+    }
+    .try_serialize(&mut data)
+    .unwrap();
+    SerializedAccount::new(Pubkey::new_unique(), &data)
+}
+
+#[test]
+fn test_realloc_grows_and_charges_payer() {
+    common::setup_syscall_stubs();
+
+    let mut log = log_account(vec![1, 2]);
+    let (log_key, log_data) = log.parts();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut log_lamports = common::minimum_balance(log_space(2));
+    let mut payer_lamports = 1_000_000_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(log_key, false, true, &mut log_lamports, log_data, &ID),
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let mut ctx = Append::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut AppendBumps::default(),
+    )
+    .unwrap();
+
+    let rent_increase =
+        common::minimum_balance(log_space(3)) - common::minimum_balance(log_space(2));
+    assert_eq!(accounts[0].data_len(), log_space(3));
+    assert_eq!(
+        accounts[0].lamports(),
+        common::minimum_balance(log_space(3))
+    );
+    assert_eq!(accounts[1].lamports(), 1_000_000_000 - rent_increase);
+
+    // The larger value fits when written back
+    ctx.log.entries.push(3);
+    ctx.exit(&ID).unwrap();
+
+    let log = Log::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(log.entries, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_realloc_shrinks_and_refunds_payer() {
+    common::setup_syscall_stubs();
+
+    let mut log = log_account(vec![1, 2, 3]);
+    let (log_key, log_data) = log.parts();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut log_lamports = common::minimum_balance(log_space(3));
+    let mut payer_lamports = 1_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(log_key, false, true, &mut log_lamports, log_data, &ID),
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let mut ctx =
+        Pop::try_accounts(&ID, &mut accounts.as_slice(), &[], &mut PopBumps::default()).unwrap();
+
+    let refund = common::minimum_balance(log_space(3)) - common::minimum_balance(log_space(2));
+    assert_eq!(accounts[0].data_len(), log_space(2));
+    assert_eq!(
+        accounts[0].lamports(),
+        common::minimum_balance(log_space(2))
+    );
+    assert_eq!(accounts[1].lamports(), 1_000 + refund);
+
+    ctx.log.entries.pop();
+    ctx.exit(&ID).unwrap();
+
+    let log = Log::try_deserialize(&accounts[0].data.borrow()).unwrap();
+    assert_eq!(log.entries, vec![1, 2]);
+}
+
+#[test]
+fn test_realloc_same_size_keeps_lamports() {
+    common::setup_syscall_stubs();
+
+    let mut log = log_account(vec![1, 2]);
+    let (log_key, log_data) = log.parts();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    // Lamports held above rent-exempt, as a vault would
+    let mut log_lamports = common::minimum_balance(log_space(2)) + 5_000;
+    let mut payer_lamports = 1_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(log_key, false, true, &mut log_lamports, log_data, &ID),
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    Rewrite::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut RewriteBumps::default(),
+    )
+    .unwrap();
+
+    assert_eq!(accounts[0].data_len(), log_space(2));
+    assert_eq!(
+        accounts[0].lamports(),
+        common::minimum_balance(log_space(2)) + 5_000
+    );
+    assert_eq!(accounts[1].lamports(), 1_000);
+}
+
+#[test]
+fn test_realloc_exceeds_limit() {
+    common::setup_syscall_stubs();
+
+    let mut log = log_account(vec![]);
+    let (log_key, log_data) = log.parts();
+    let payer_key = Pubkey::new_unique();
+    let system_program_id = system_program::ID;
+
+    let mut log_lamports = common::minimum_balance(log_space(0));
+    let mut payer_lamports = 1_000_000_000u64;
+    let mut payer_data = vec![0u8; 0];
+    let mut system_lamports = 1u64;
+    let mut system_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(log_key, false, true, &mut log_lamports, log_data, &ID),
+        create_account_info(
+            &payer_key,
+            true,
+            true,
+            &mut payer_lamports,
+            &mut payer_data,
+            &system_program_id,
+        ),
+        common::program_account_info(&system_program_id, &mut system_lamports, &mut system_data),
+    ];
+
+    let result = Grow::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut GrowBumps::default(),
+    );
+    assert_eq!(
+        result.err(),
+        Some(ErrorCode::AccountReallocExceedsLimit.into())
+    );
+    assert_eq!(accounts[0].data_len(), log_space(0));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::{account_data, create_account_info};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{declare_id, Account, Accounts, UncheckedAccount};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    #[account(mut, signer)]
+    pub counter: Account<'info, Counter>,
+    /// CHECK: only required to sign
+    #[account(signer)]
+    pub approver: UncheckedAccount<'info>,
+}
+
+// Runs try_accounts with the given signer and writable flags of the counter,
+// and whether the approver signed
+fn try_approve(
+    counter_signed: bool,
+    counter_writable: bool,
+    approver_signed: bool,
+) -> Result<(), ProgramError> {
+    let counter_key = Pubkey::new_unique();
+    let approver_key = Pubkey::new_unique();
+    let system_program_id = solana_program::system_program::ID;
+
+    let mut counter_lamports = 1000u64;
+    let mut counter_data = account_data(Counter { count: 0 });
+    let mut approver_lamports = 1000u64;
+    let mut approver_data = vec![0u8; 0];
+
+    let accounts = vec![
+        create_account_info(
+            &counter_key,
+            counter_signed,
+            counter_writable,
+            &mut counter_lamports,
+            &mut counter_data,
+            &ID,
+        ),
+        create_account_info(
+            &approver_key,
+            approver_signed,
+            false,
+            &mut approver_lamports,
+            &mut approver_data,
+            &system_program_id,
+        ),
+    ];
+
+    Approve::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut ApproveBumps::default(),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_signer_constraint() {
+    try_approve(true, true, true).unwrap();
+}
+
+#[test]
+fn test_signer_constraint_violated() {
+    assert_eq!(
+        try_approve(false, true, true).err(),
+        Some(ErrorCode::ConstraintSigner.into())
+    );
+    assert_eq!(
+        try_approve(true, true, false).err(),
+        Some(ErrorCode::ConstraintSigner.into())
+    );
+}
+
+#[test]
+fn test_mut_checked_before_signer() {
+    assert_eq!(
+        try_approve(false, false, true).err(),
+        Some(ErrorCode::ConstraintMut.into())
+    );
+}
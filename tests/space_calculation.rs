@@ -1,3 +1,5 @@
+mini_anchor::declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
 #[mini_anchor::account]
 pub struct TestVecSpace {
     #[max_len(10)]
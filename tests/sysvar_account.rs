@@ -0,0 +1,106 @@
+mod common;
+
+use common::create_account_info;
+use mini_anchor::error::ErrorCode;
+use mini_anchor::system_program;
+use mini_anchor::{declare_id, Accounts, SystemAccount, Sysvar};
+use solana_program::{
+    clock::Clock,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::{self, Sysvar as _},
+};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[derive(Accounts)]
+pub struct Tick<'info> {
+    pub wallet: SystemAccount<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+// Runs try_accounts with the given wallet owner and sysvar keys, returning the clock's slot
+fn try_tick(
+    wallet_owner: Pubkey,
+    clock_key: Pubkey,
+    rent_key: Pubkey,
+) -> Result<u64, ProgramError> {
+    let wallet_key = Pubkey::new_unique();
+    let sysvar_owner = sysvar::ID;
+
+    let mut wallet_lamports = 1000u64;
+    let mut wallet_data = vec![0u8; 0];
+    let mut clock_lamports = 1u64;
+    let mut clock_data = vec![0u8; Clock::size_of()];
+    let mut rent_lamports = 1u64;
+    let mut rent_data = vec![0u8; Rent::size_of()];
+
+    let mut clock_info = create_account_info(
+        &clock_key,
+        false,
+        false,
+        &mut clock_lamports,
+        &mut clock_data,
+        &sysvar_owner,
+    );
+    Clock {
+        slot: 42,
+        ..Clock::default()
+    }
+    .to_account_info(&mut clock_info)
+    .unwrap();
+
+    let mut rent_info = create_account_info(
+        &rent_key,
+        false,
+        false,
+        &mut rent_lamports,
+        &mut rent_data,
+        &sysvar_owner,
+    );
+    Rent::default().to_account_info(&mut rent_info).unwrap();
+
+    let wallet_info = create_account_info(
+        &wallet_key,
+        false,
+        false,
+        &mut wallet_lamports,
+        &mut wallet_data,
+        &wallet_owner,
+    );
+
+    let accounts = vec![wallet_info, clock_info, rent_info];
+
+    let ctx = Tick::try_accounts(
+        &ID,
+        &mut accounts.as_slice(),
+        &[],
+        &mut TickBumps::default(),
+    )?;
+    assert_eq!(
+        ctx.rent.lamports_per_byte_year,
+        Rent::default().lamports_per_byte_year
+    );
+    Ok(ctx.clock.slot)
+}
+
+#[test]
+fn test_system_account_and_sysvars() {
+    let slot = try_tick(system_program::ID, sysvar::clock::ID, sysvar::rent::ID).unwrap();
+    assert_eq!(slot, 42);
+}
+
+#[test]
+fn test_system_account_wrong_owner() {
+    let result = try_tick(ID, sysvar::clock::ID, sysvar::rent::ID);
+    assert_eq!(result, Err(ErrorCode::AccountNotSystemOwned.into()));
+}
+
+#[test]
+fn test_sysvar_mismatch() {
+    // Rent passed where the clock is expected
+    let result = try_tick(system_program::ID, sysvar::rent::ID, sysvar::rent::ID);
+    assert_eq!(result, Err(ErrorCode::AccountSysvarMismatch.into()));
+}
@@ -0,0 +1,10 @@
+use mini_anchor::declare_id;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Profile {
+    pub name: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use mini_anchor::declare_id;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter(u64);
+
+fn main() {}
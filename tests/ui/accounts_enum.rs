@@ -0,0 +1,8 @@
+use mini_anchor::Accounts;
+
+#[derive(Accounts)]
+pub enum Initialize {
+    Empty,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use mini_anchor::system_program::System;
+use mini_anchor::{declare_id, Account, Accounts, Program};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(init, payer = user)]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub user: Account<'info, Counter>,
+    pub system_program: Program<'info, System>,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use mini_anchor::{Accounts, UncheckedAccount};
+
+#[derive(Accounts)]
+pub struct Forward<'info> {
+    pub target: UncheckedAccount<'info>,
+}
+
+fn main() {}
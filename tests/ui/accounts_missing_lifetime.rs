@@ -0,0 +1,6 @@
+use mini_anchor::Accounts;
+
+#[derive(Accounts)]
+pub struct Initialize {}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use mini_anchor::{Accounts, Signer};
+
+#[derive(Accounts)]
+pub struct Auth<'info> {
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    pub auth: Option<Auth<'info>>,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use mini_anchor::{Accounts, Signer};
+
+#[derive(Accounts)]
+pub struct Auth<'info> {
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Update<'info> {
+    #[account(mut)]
+    pub auth: Auth<'info>,
+}
+
+fn main() {}
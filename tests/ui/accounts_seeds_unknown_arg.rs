@@ -0,0 +1,16 @@
+use mini_anchor::{declare_id, Account, Accounts};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Profile {
+    pub age: u8,
+}
+
+#[derive(Accounts)]
+pub struct CreateProfile<'info> {
+    #[account(seeds = [b"profile", name.as_bytes()], bump)]
+    pub profile: Account<'info, Profile>,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use mini_anchor::{Accounts, Signer};
+
+#[derive(Accounts)]
+pub struct Initialize<'info>(Signer<'info>);
+
+fn main() {}
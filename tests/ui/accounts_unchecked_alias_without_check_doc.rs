@@ -0,0 +1,10 @@
+use mini_anchor::{Accounts, UncheckedAccount};
+
+pub type Anything<'info> = UncheckedAccount<'info>;
+
+#[derive(Accounts)]
+pub struct Forward<'info> {
+    pub target: Anything<'info>,
+}
+
+fn main() {}
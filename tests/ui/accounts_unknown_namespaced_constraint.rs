@@ -0,0 +1,18 @@
+use mini_anchor::{declare_id, Account, Accounts, Signer};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Resize<'info> {
+    #[account(mut, realloc = 64, realloc::payr = payer)]
+    pub counter: Account<'info, Counter>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+fn main() {}
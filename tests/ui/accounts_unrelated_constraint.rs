@@ -0,0 +1,16 @@
+use mini_anchor::{declare_id, Account, Accounts};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Increment<'info> {
+    #[account(writable)]
+    pub counter: Account<'info, Counter>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use mini_anchor::Accounts;
+use solana_program::account_info::AccountInfo;
+
+#[derive(Accounts)]
+pub struct Forward<'info> {
+    pub target: &'info AccountInfo<'info>,
+}
+
+fn main() {}
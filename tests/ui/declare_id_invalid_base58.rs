@@ -0,0 +1,5 @@
+use mini_anchor::declare_id;
+
+declare_id!("not-base58-0OIl");
+
+fn main() {}
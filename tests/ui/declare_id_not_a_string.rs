@@ -0,0 +1,5 @@
+use mini_anchor::declare_id;
+
+declare_id!(Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS);
+
+fn main() {}
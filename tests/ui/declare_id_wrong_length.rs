@@ -0,0 +1,5 @@
+use mini_anchor::declare_id;
+
+declare_id!("11111111");
+
+fn main() {}
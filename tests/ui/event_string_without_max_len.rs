@@ -0,0 +1,8 @@
+use mini_anchor::event;
+
+#[event]
+pub struct Renamed {
+    pub name: String,
+}
+
+fn main() {}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{account_data, create_account_info, run_accounts};
+use mini_anchor::error::ErrorCode;
+use mini_anchor::{declare_id, Account, Accounts};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+#[mini_anchor::account]
+pub struct Counter {
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(zero)]
+    pub counter: Account<'info, Counter>,
+}
+
+// Runs try_accounts on a counter account with the given state, then exits
+fn try_initialize(data: &mut [u8], lamports: u64, owner: Pubkey) -> Result<(), ProgramError> {
+    common::setup_syscall_stubs();
+
+    let key = Pubkey::new_unique();
+    let mut lamports = lamports;
+    let accounts = vec![create_account_info(
+        &key,
+        false,
+        true,
+        &mut lamports,
+        data,
+        &owner,
+    )];
+
+    run_accounts(
+        &mut accounts.as_slice(),
+        &mut InitializeBumps::default(),
+        |ctx: &mut Initialize| ctx.counter.count = 1,
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn test_zero_initializes_account() {
+    let mut data = vec![0u8; Counter::SPACE];
+    let rent = common::minimum_balance(Counter::SPACE);
+
+    try_initialize(&mut data, rent, ID).unwrap();
+
+    assert_eq!(data[..8], Counter::DISCRIMINATOR);
+    let counter = Counter::try_deserialize(&data).unwrap();
+    assert_eq!(counter.count, 1);
+}
+
+#[test]
+fn test_zero_rejects_initialized_account() {
+    let mut data = account_data(Counter {
+        authority: Pubkey::new_unique(),
+        count: 5,
+    });
+    let rent = common::minimum_balance(Counter::SPACE);
+
+    let result = try_initialize(&mut data, rent, ID);
+    assert_eq!(result, Err(ErrorCode::ConstraintZero.into()));
+
+    // Existing data is left alone
+    assert_eq!(Counter::try_deserialize(&data).unwrap().count, 5);
+}
+
+#[test]
+fn test_zero_requires_rent_exemption() {
+    let mut data = vec![0u8; Counter::SPACE];
+    let result = try_initialize(&mut data, 1, ID);
+    assert_eq!(result, Err(ErrorCode::ConstraintRentExempt.into()));
+}
+
+#[test]
+fn test_zero_requires_program_owner() {
+    let mut data = vec![0u8; Counter::SPACE];
+    let rent = common::minimum_balance(Counter::SPACE);
+
+    let result = try_initialize(&mut data, rent, Pubkey::new_unique());
+    assert_eq!(result, Err(ErrorCode::AccountOwnedByWrongProgram.into()));
+    assert_eq!(data, vec![0u8; Counter::SPACE]);
+}